@@ -1,9 +1,17 @@
 use crate::core::beatmap::{
     apply_beatmap_modifications_core, emit_demo_beatmap_core, get_all_rates_from_state,
-    get_current_beatmap_from_state, get_current_nps_data,
+    get_current_beatmap_from_state, get_current_density_data, get_current_nps_data,
+    get_current_nps_data_with, get_current_pattern_data, load_current_parsed_beatmap,
 };
-use crate::core::calc::nps::calculate_nps_graph;
-use crate::core::react::{BeatmapModifications, Beatmapset, NpsData, Rates};
+use crate::core::calc::error::CalcError;
+use crate::core::calc::nps::{calculate_nps_graph, NpsOptions};
+use crate::core::calc::timeline::{calculate_difficulty_timeline, TimelineOptions};
+use crate::core::error::{AppError, AppResult, ErrorCode};
+use crate::core::react::{
+    BeatmapModifications, Beatmapset, DensityData, DifficultyTimeline, GeneratedBeatmap, NpsData,
+    PatternData, Rates,
+};
+use minacalc_rs::Calc;
 use reqwest;
 use tauri::AppHandle;
 use rosu_map::Beatmap as RmBeatmap;
@@ -11,20 +19,58 @@ use std::str::FromStr;
 
 // Command to get the current beatmap from the global state
 #[tauri::command]
-pub async fn get_current_beatmap(app_handle: AppHandle) -> Result<Option<Beatmapset>, String> {
+pub async fn get_current_beatmap(app_handle: AppHandle) -> AppResult<Option<Beatmapset>> {
     Ok(get_current_beatmap_from_state(&app_handle).await)
 }
 
 // Command to get all rates for the current beatmap
 #[tauri::command]
-pub async fn get_all_rates(app_handle: AppHandle) -> Result<Vec<Rates>, String> {
+pub async fn get_all_rates(app_handle: AppHandle) -> AppResult<Vec<Rates>> {
     Ok(get_all_rates_from_state(&app_handle).await)
 }
 
-// Command to get NPS data for the current beatmap
+// Command to get NPS data for the current beatmap, recomputed when options are given
+#[tauri::command]
+pub async fn get_current_nps(
+    app_handle: AppHandle,
+    options: Option<NpsOptions>,
+) -> AppResult<Option<NpsData>> {
+    match options {
+        Some(options) => get_current_nps_data_with(&app_handle, &options).await,
+        None => Ok(get_current_nps_data(&app_handle).await),
+    }
+}
+
+// Command to get the density analysis for the current beatmap
 #[tauri::command]
-pub async fn get_current_nps(app_handle: AppHandle) -> Result<Option<NpsData>, String> {
-    Ok(get_current_nps_data(&app_handle).await)
+pub async fn get_current_density(app_handle: AppHandle) -> AppResult<Option<DensityData>> {
+    Ok(get_current_density_data(&app_handle).await)
+}
+
+// Command to get the pattern timeline for the current beatmap
+#[tauri::command]
+pub async fn get_current_patterns(app_handle: AppHandle) -> AppResult<Option<PatternData>> {
+    Ok(get_current_pattern_data(&app_handle).await)
+}
+
+// Command to compute the local MSD timeline of the current beatmap
+#[tauri::command]
+pub async fn get_current_difficulty_timeline(
+    app_handle: AppHandle,
+    options: Option<TimelineOptions>,
+) -> AppResult<Option<DifficultyTimeline>> {
+    let Some(beatmap) = load_current_parsed_beatmap(&app_handle).await? else {
+        return Ok(None);
+    };
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let calc = Calc::new().map_err(CalcError::calc)?;
+        let timeline = calculate_difficulty_timeline(&calc, &beatmap, &options)?;
+        Ok(Some(timeline))
+    })
+    .await
+    .map_err(|e| AppError::task("Difficulty timeline", e))?
 }
 
 // Command to apply modifications to a beatmap and create a copy
@@ -32,39 +78,35 @@ pub async fn get_current_nps(app_handle: AppHandle) -> Result<Option<NpsData>, S
 pub async fn apply_beatmap_modifications(
     app_handle: AppHandle,
     modifications: BeatmapModifications,
-) -> Result<Beatmapset, String> {
+) -> AppResult<GeneratedBeatmap> {
     apply_beatmap_modifications_core(&app_handle, modifications).await
 }
 
 // Demo command to emit a beatmap with an incremented counter
 #[tauri::command]
-pub async fn emit_demo_beatmap(app_handle: AppHandle, counter: u32) -> Result<(), String> {
+pub async fn emit_demo_beatmap(app_handle: AppHandle, counter: u32) -> AppResult<()> {
     emit_demo_beatmap_core(&app_handle, counter)
 }
 
 // Command to calculate NPS from a beatmap URL (for remote beatmaps)
 #[tauri::command]
-pub async fn calculate_nps_from_beatmap_url(beatmap_url: String) -> Result<NpsData, String> {
+pub async fn calculate_nps_from_beatmap_url(beatmap_url: String) -> AppResult<NpsData> {
     // Download the .osu file content
     let client = reqwest::Client::new();
-    let response = client
-        .get(&beatmap_url)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to download: {}", e))?;
+    let response = client.get(&beatmap_url).send().await?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        return Err(
+            AppError::new(ErrorCode::Network, format!("HTTP error: {}", response.status()))
+                .with_context(beatmap_url),
+        );
     }
 
-    let osu_content = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read content: {}", e))?;
+    let osu_content = response.text().await?;
 
     // Parse the beatmap
-    let parsed_beatmap =
-        RmBeatmap::from_str(&osu_content).map_err(|e| format!("Failed to parse beatmap: {}", e))?;
+    let parsed_beatmap = RmBeatmap::from_str(&osu_content)
+        .map_err(|e| AppError::parse("beatmap", e).with_context(beatmap_url))?;
 
     // Calculate NPS
     let (nps_graph, drain_time) = calculate_nps_graph(&parsed_beatmap);
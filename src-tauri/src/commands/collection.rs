@@ -0,0 +1,34 @@
+use crate::core::collection::{
+    create_pack_from_collection as core_create_pack_from_collection,
+    download_missing_from_collection as core_download_missing_from_collection,
+    list_collections as core_list_collections, CollectionDownload, CollectionSummary,
+};
+use crate::core::error::{AppError, AppResult};
+use crate::core::packmaker::SharedPackMaker;
+use tauri::{AppHandle, Manager};
+
+#[tauri::command]
+pub async fn list_collections(path: Option<String>) -> AppResult<Vec<CollectionSummary>> {
+    tauri::async_runtime::spawn_blocking(move || core_list_collections(path))
+        .await
+        .map_err(|e| AppError::task("Collection listing", e))?
+}
+
+#[tauri::command]
+pub async fn create_pack_from_collection(
+    app_handle: AppHandle,
+    path: Option<String>,
+    name: String,
+) -> AppResult<usize> {
+    let pack = app_handle.state::<SharedPackMaker>();
+    core_create_pack_from_collection(&pack, path, name).await
+}
+
+#[tauri::command]
+pub async fn download_missing_from_collection(
+    app_handle: AppHandle,
+    path: Option<String>,
+    name: String,
+) -> AppResult<CollectionDownload> {
+    core_download_missing_from_collection(app_handle, path, name).await
+}
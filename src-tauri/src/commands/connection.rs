@@ -1,9 +1,18 @@
-use crate::core::connection::restart_osu_connection as core_restart_osu_connection;
+use crate::core::connection::{
+    restart_osu_connection as core_restart_osu_connection,
+    stop_osu_connection as core_stop_osu_connection,
+};
+use crate::core::error::AppResult;
 use tauri::AppHandle;
 
 #[tauri::command]
-pub async fn restart_osu_connection(app_handle: AppHandle) -> Result<String, String> {
+pub async fn restart_osu_connection(app_handle: AppHandle) -> AppResult<String> {
     core_restart_osu_connection(app_handle)
         .await
         .map(|_| "Reconnection started".to_string())
 }
+
+#[tauri::command]
+pub async fn stop_osu_connection(app_handle: AppHandle) -> AppResult<()> {
+    core_stop_osu_connection(app_handle).await
+}
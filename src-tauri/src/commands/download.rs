@@ -1,22 +1,54 @@
 use crate::core::download as download_core;
+use crate::core::download::PackManifestEntry;
+use crate::core::error::AppResult;
 use tauri::AppHandle;
 
 #[tauri::command]
 pub async fn download_beatmap_from_url(
     app_handle: AppHandle,
     url: String,
-    filename: String,
+    beatmapset_id: Option<i32>,
+    filename: Option<String>,
     beatmapset_name: String,
     creator: String,
-) -> Result<String, String> {
-    download_core::download_beatmap_from_url(app_handle, url, filename, beatmapset_name, creator)
-        .await
+) -> AppResult<String> {
+    download_core::download_beatmap_from_url(
+        app_handle,
+        url,
+        beatmapset_id,
+        filename,
+        beatmapset_name,
+        creator,
+    )
+    .await
 }
 
 #[tauri::command]
-pub async fn test_download_event(
+pub async fn download_beatmapsets(
     app_handle: AppHandle,
-    beatmapset_id: i32,
-) -> Result<String, String> {
+    name: String,
+    beatmapset_ids: Vec<i32>,
+) -> AppResult<u64> {
+    let entries = beatmapset_ids
+        .into_iter()
+        .map(|beatmapset_id| PackManifestEntry {
+            beatmapset_id,
+            title: None,
+            creator: None,
+        })
+        .collect();
+    download_core::download_beatmapsets(app_handle, name, entries).await
+}
+
+#[tauri::command]
+pub async fn download_pack_manifest(
+    app_handle: AppHandle,
+    manifest_path: String,
+) -> AppResult<u64> {
+    download_core::download_pack_manifest(app_handle, manifest_path).await
+}
+
+#[tauri::command]
+pub async fn test_download_event(app_handle: AppHandle, beatmapset_id: i32) -> AppResult<String> {
     download_core::test_download_event(app_handle, beatmapset_id).await
 }
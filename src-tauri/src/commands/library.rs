@@ -0,0 +1,24 @@
+use crate::core::error::{AppError, AppResult};
+use crate::core::library::{load_osu_db, resolve_md5};
+use crate::core::preferences;
+use crate::core::stable::osu_db::OsuDbBeatmap;
+
+#[tauri::command]
+pub async fn get_library() -> AppResult<Vec<OsuDbBeatmap>> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let songs_path = preferences::songs_path();
+        load_osu_db(&songs_path).map(|db| db.beatmaps.clone())
+    })
+    .await
+    .map_err(|e| AppError::task("Library loading", e))?
+}
+
+#[tauri::command]
+pub async fn resolve_beatmap_md5(md5: String) -> AppResult<Option<String>> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let songs_path = preferences::songs_path();
+        resolve_md5(&songs_path, &md5).map(|path| path.map(|p| p.to_string_lossy().into_owned()))
+    })
+    .await
+    .map_err(|e| AppError::task("Beatmap lookup", e))?
+}
@@ -1,5 +1,10 @@
 pub mod beatmap;
+pub mod collection;
 pub mod connection;
 pub mod download;
+pub mod library;
 pub mod preferences;
+pub mod profile;
+pub mod recommend;
 pub mod packmaker;
+pub mod replay;
@@ -2,9 +2,10 @@ use tauri::{AppHandle, Manager};
 use crate::core::packmaker::{SharedPackMaker, PackMetadata, get_public_pack, update_pack_beatmap_version, PackBeatmapModifications, update_pack_beatmap, update_pack_beatmap_modifications, apply_pack_beatmap_modifications, get_pack_beatmap_modifications};
 use crate::core::beatmap::monitoring::CurrentBeatmapWithRates;
 use crate::core::packmaker::add_current_to_pack;
+use crate::core::error::AppResult;
 
 #[tauri::command]
-pub async fn add_to_pack(app_handle: AppHandle) -> Result<(), String> {
+pub async fn add_to_pack(app_handle: AppHandle) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     let current = app_handle.state::<CurrentBeatmapWithRates>();
     let guard = current.lock().await;
@@ -12,25 +13,25 @@ pub async fn add_to_pack(app_handle: AppHandle) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn get_pack(app_handle: AppHandle) -> Result<(PackMetadata, Vec<crate::core::packmaker::PublicBeatmapData>), String> {
+pub async fn get_pack(app_handle: AppHandle) -> AppResult<(PackMetadata, Vec<crate::core::packmaker::PublicBeatmapData>)> {
     let pack = app_handle.state::<SharedPackMaker>();
     Ok(get_public_pack(&pack).await)
 }
 
 #[tauri::command]
-pub async fn update_pack_beatmap_version_cmd(app_handle: AppHandle, index: usize, new_version: String) -> Result<(), String> {
+pub async fn update_pack_beatmap_version_cmd(app_handle: AppHandle, index: usize, new_version: String) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     update_pack_beatmap_version(&pack, index, new_version).await
 }
 
 #[tauri::command]
-pub async fn update_pack_beatmap_cmd(app_handle: AppHandle, index: usize, modifications: PackBeatmapModifications) -> Result<(), String> {
+pub async fn update_pack_beatmap_cmd(app_handle: AppHandle, index: usize, modifications: PackBeatmapModifications) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     update_pack_beatmap(&pack, index, modifications).await
 }
 
 #[tauri::command]
-pub async fn update_pack_metadata(app_handle: AppHandle, metadata: PackMetadata) -> Result<(), String> {
+pub async fn update_pack_metadata(app_handle: AppHandle, metadata: PackMetadata) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     let mut guard = pack.lock().await;
     guard.metadata = metadata;
@@ -38,19 +39,19 @@ pub async fn update_pack_metadata(app_handle: AppHandle, metadata: PackMetadata)
 }
 
 #[tauri::command]
-pub async fn update_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize, modifications: PackBeatmapModifications) -> Result<(), String> {
+pub async fn update_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize, modifications: PackBeatmapModifications) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     update_pack_beatmap_modifications(&pack, index, modifications).await
 }
 
 #[tauri::command]
-pub async fn apply_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize) -> Result<(), String> {
+pub async fn apply_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize) -> AppResult<()> {
     let pack = app_handle.state::<SharedPackMaker>();
     apply_pack_beatmap_modifications(&pack, index).await
 }
 
 #[tauri::command]
-pub async fn get_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize) -> Result<PackBeatmapModifications, String> {
+pub async fn get_pack_beatmap_modifications_cmd(app_handle: AppHandle, index: usize) -> AppResult<PackBeatmapModifications> {
     let pack = app_handle.state::<SharedPackMaker>();
     get_pack_beatmap_modifications(&pack, index).await
 }
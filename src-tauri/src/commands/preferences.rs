@@ -1,21 +1,42 @@
+use crate::core::error::AppResult;
 use crate::core::preferences;
 
 #[tauri::command]
-pub fn get_songs_path() -> Result<String, String> {
+pub fn get_songs_path() -> AppResult<String> {
     preferences::get_songs_path()
 }
 
 #[tauri::command]
-pub fn set_songs_path(path: String) -> Result<(), String> {
+pub fn set_songs_path(path: String) -> AppResult<()> {
     preferences::set_songs_path(path)
 }
 
 #[tauri::command]
-pub fn get_theme() -> Result<String, String> {
+pub fn get_theme() -> AppResult<String> {
     preferences::get_theme()
 }
 
 #[tauri::command]
-pub fn set_theme(theme: String) -> Result<(), String> {
+pub fn set_theme(theme: String) -> AppResult<()> {
     preferences::set_theme(theme)
 }
+
+#[tauri::command]
+pub fn get_write_generated_to_collection() -> AppResult<bool> {
+    preferences::get_write_generated_to_collection()
+}
+
+#[tauri::command]
+pub fn set_write_generated_to_collection(enabled: bool) -> AppResult<()> {
+    preferences::set_write_generated_to_collection(enabled)
+}
+
+#[tauri::command]
+pub fn get_generated_collection_name() -> AppResult<String> {
+    preferences::get_generated_collection_name()
+}
+
+#[tauri::command]
+pub fn set_generated_collection_name(name: String) -> AppResult<()> {
+    preferences::set_generated_collection_name(name)
+}
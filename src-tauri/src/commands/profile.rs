@@ -0,0 +1,6 @@
+use crate::core::profile::{load_profile, PlayerProfile};
+
+#[tauri::command]
+pub fn get_player_profile() -> PlayerProfile {
+    load_profile()
+}
@@ -0,0 +1,16 @@
+use crate::core::error::{AppError, AppResult};
+use crate::core::recommend::{
+    recommend_maps as core_recommend_maps, RecommendOptions, Recommendation,
+};
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn recommend_maps(
+    app_handle: AppHandle,
+    options: RecommendOptions,
+) -> AppResult<Vec<Recommendation>> {
+    // MSD calculation is CPU heavy and the calculator is not Send, keep it off the async workers
+    tauri::async_runtime::spawn_blocking(move || core_recommend_maps(&app_handle, options))
+        .await
+        .map_err(|e| AppError::task("Recommendation", e))?
+}
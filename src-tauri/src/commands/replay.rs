@@ -0,0 +1,33 @@
+use crate::core::calc::wife::{rescore, WifeRescore};
+use crate::core::error::{AppError, AppResult};
+use crate::core::replay::{
+    analyze_replay as core_analyze_replay, list_replays as core_list_replays,
+    rescore_replay as core_rescore_replay, ReplayAnalysis, ReplayRescore, ReplaySummary,
+};
+
+#[tauri::command]
+pub async fn list_replays() -> AppResult<Vec<ReplaySummary>> {
+    tauri::async_runtime::spawn_blocking(core_list_replays)
+        .await
+        .map_err(|e| AppError::task("Replay listing", e))?
+}
+
+#[tauri::command]
+pub async fn analyze_replay(path: String) -> AppResult<ReplayAnalysis> {
+    tauri::async_runtime::spawn_blocking(move || core_analyze_replay(&path))
+        .await
+        .map_err(|e| AppError::task("Replay analysis", e))?
+}
+
+#[tauri::command]
+pub async fn rescore_replay(path: String) -> AppResult<ReplayRescore> {
+    tauri::async_runtime::spawn_blocking(move || core_rescore_replay(&path))
+        .await
+        .map_err(|e| AppError::task("Replay rescoring", e))?
+}
+
+// Rescore raw hit offsets in ms (`None` = miss), e.g. from live gameplay
+#[tauri::command]
+pub fn rescore_offsets(offsets: Vec<Option<f64>>, dropped_holds: usize) -> WifeRescore {
+    rescore(&offsets, dropped_holds)
+}
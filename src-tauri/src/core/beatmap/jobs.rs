@@ -0,0 +1,252 @@
+use super::monitoring::{
+    emit_bpm_calculated, emit_density_calculated, emit_nps_calculated, emit_patterns_calculated,
+    emit_rates_calculated, CurrentBeatmapWithRates,
+};
+use crate::core::calc::bpm::calculate_bpm_stats;
+use crate::core::calc::density::calculate_density;
+use crate::core::calc::error::{CalcError, CalcFailure};
+use crate::core::calc::etterna::process_beatmap_cancellable;
+use crate::core::calc::nps::calculate_nps_graph;
+use crate::core::calc::patterns::classify_patterns;
+use crate::core::error::AppError;
+use crate::core::profile::play::{rate_play, FinishedPlay};
+use crate::core::profile::record_play;
+use crate::core::react::{BpmStats, DensityData, NpsData, PatternData, Rates};
+use minacalc_rs::Calc;
+use rosu_map::section::general::GameMode;
+use rosu_memory_lib::reader::beatmap::common::BeatmapInfo;
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+
+/// Results of rating a beatmap, tagged with the md5 they belong to
+struct BeatmapAnalysis {
+    md5: String,
+    rates: Vec<Rates>,
+    nps_data: NpsData,
+    density_data: Option<DensityData>,
+    pattern_data: Option<PatternData>,
+    bpm: BpmStats,
+}
+
+thread_local! {
+    /// Calculator of the blocking thread, created on its first job
+    static CALC: RefCell<Option<Calc>> = const { RefCell::new(None) };
+}
+
+/// A running job and the flag asking it to stop
+struct RunningJob {
+    md5: String,
+    cancelled: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+/// Runs the rating of the detected beatmap in the background, one job at a time
+#[derive(Default)]
+pub struct CalcJobs {
+    current: Option<RunningJob>,
+}
+
+impl CalcJobs {
+    /// Cancel the running job, if any, and start rating the beatmap at `osu_path`
+    pub fn start(
+        &mut self,
+        app_handle: AppHandle,
+        current_beatmap: CurrentBeatmapWithRates,
+        md5: String,
+        osu_path: String,
+    ) {
+        self.cancel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let handle = tauri::async_runtime::spawn(run_job(
+            app_handle,
+            current_beatmap,
+            md5.clone(),
+            osu_path,
+            cancelled.clone(),
+        ));
+        self.current = Some(RunningJob {
+            md5,
+            cancelled,
+            handle,
+        });
+    }
+
+    /// Stop the running job at its next stage, its results are dropped
+    pub fn cancel(&mut self) {
+        if let Some(job) = self.current.take() {
+            job.cancelled.store(true, Ordering::Relaxed);
+            job.handle.abort();
+            println!("🛑 Cancelled calculation for {}", job.md5);
+        }
+    }
+}
+
+impl Drop for CalcJobs {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Rate the beatmap on the blocking pool and store the results if it is still the current one
+async fn run_job(
+    app_handle: AppHandle,
+    current_beatmap: CurrentBeatmapWithRates,
+    md5: String,
+    osu_path: String,
+    cancelled: Arc<AtomicBool>,
+) {
+    println!("🧮 Processing beatmap for rates & NPS: {}", osu_path);
+    let job_md5 = md5.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        analyze_beatmap(job_md5, &osu_path, &cancelled)
+    })
+    .await
+    .map_err(CalcError::calc)
+    .and_then(|result| result);
+
+    let analysis = match result {
+        Ok(analysis) => analysis,
+        Err(CalcError::Cancelled) => {
+            println!("🛑 Calculation stopped for {}", md5);
+            return;
+        }
+        Err(error) => {
+            eprintln!("❌ {}", error);
+            emit_calc_error(&app_handle, md5, error);
+            return;
+        }
+    };
+
+    {
+        let mut current = current_beatmap.lock().await;
+        let current_md5 = current
+            .beatmap_info
+            .as_ref()
+            .map(|info| info.technical.md5.as_str());
+        if current_md5 != Some(analysis.md5.as_str()) {
+            println!("🗑️ Discarding stale results for {}", analysis.md5);
+            return;
+        }
+        current.rates = analysis.rates.clone();
+        current.nps_data = Some(analysis.nps_data.clone());
+        current.density_data = analysis.density_data.clone();
+        current.pattern_data = analysis.pattern_data.clone();
+        current.bpm = Some(analysis.bpm.clone());
+    }
+
+    emit_rates_calculated(&app_handle, &analysis.rates);
+    emit_bpm_calculated(&app_handle, &analysis.bpm);
+    emit_nps_calculated(&app_handle, &analysis.nps_data);
+    if let Some(density_data) = &analysis.density_data {
+        emit_density_calculated(&app_handle, density_data);
+    }
+    if let Some(pattern_data) = &analysis.pattern_data {
+        emit_patterns_calculated(&app_handle, pattern_data);
+    }
+}
+
+/// Rate a finished play and add it to the profile on the blocking pool
+///
+/// Unlike beatmap jobs these are never cancelled, every play is recorded.
+pub fn record_finished_play(
+    app_handle: AppHandle,
+    beatmap_info: BeatmapInfo,
+    osu_path: String,
+    play: FinishedPlay,
+) {
+    tauri::async_runtime::spawn(async move {
+        let result = tauri::async_runtime::spawn_blocking(move || {
+            let record = with_calc(|calc| rate_play(calc, &beatmap_info, &osu_path, &play))?;
+            println!(
+                "📝 Play recorded: {} at {:.2}x, {:.2}% -> {:.2} SSR",
+                record.beatmap_name,
+                record.centirate as f64 / 100.0,
+                record.wife_percent,
+                record.overall
+            );
+            record_play(record)
+        })
+        .await
+        .map_err(|e| AppError::task("Play rating", e))
+        .and_then(|result| result);
+
+        match result {
+            Ok(profile) => {
+                if let Err(e) = app_handle.emit("profile-updated", profile) {
+                    eprintln!("Failed to emit profile: {}", e);
+                }
+            }
+            Err(e) => eprintln!("⚠️  Play not recorded: {}", e),
+        }
+    });
+}
+
+fn emit_calc_error(app_handle: &AppHandle, md5: String, error: CalcError) {
+    let failure = CalcFailure {
+        md5,
+        message: error.to_string(),
+        error,
+    };
+    if let Err(e) = app_handle.emit("calc-error", failure) {
+        eprintln!("Failed to emit calc error: {}", e);
+    }
+}
+
+/// Run `f` with the calculator of the current thread, creating it if needed
+fn with_calc<T, E: From<CalcError>>(f: impl FnOnce(&Calc) -> Result<T, E>) -> Result<T, E> {
+    CALC.with(|cell| {
+        let mut slot = cell.borrow_mut();
+        if slot.is_none() {
+            *slot = Some(Calc::new().map_err(CalcError::calc)?);
+        }
+        f(slot.as_ref().expect("calculator is initialised"))
+    })
+}
+
+fn analyze_beatmap(
+    md5: String,
+    osu_path: &str,
+    cancelled: &AtomicBool,
+) -> Result<BeatmapAnalysis, CalcError> {
+    let check_cancelled = || {
+        if cancelled.load(Ordering::Relaxed) {
+            Err(CalcError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
+    let (rates, parsed_beatmap) =
+        with_calc(|calc| process_beatmap_cancellable(calc, osu_path, cancelled))?;
+    println!("✅ Rates calculated: {} entries", rates.len());
+
+    let (nps_graph, drain_time) = calculate_nps_graph(&parsed_beatmap);
+    println!(
+        "✅ NPS calculated: {} points, drain_time={}s",
+        nps_graph.len(),
+        drain_time
+    );
+    check_cancelled()?;
+
+    // Column based analyses only apply to osu!mania charts
+    let is_mania = parsed_beatmap.mode == GameMode::Mania;
+    let density_data = is_mania.then(|| calculate_density(&parsed_beatmap));
+    check_cancelled()?;
+    let pattern_data = is_mania.then(|| classify_patterns(&parsed_beatmap));
+    check_cancelled()?;
+
+    Ok(BeatmapAnalysis {
+        md5,
+        rates,
+        nps_data: NpsData {
+            nps_graph,
+            drain_time,
+        },
+        density_data,
+        pattern_data,
+        bpm: calculate_bpm_stats(&parsed_beatmap),
+    })
+}
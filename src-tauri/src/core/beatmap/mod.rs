@@ -1,10 +1,17 @@
 pub mod detection;
+pub mod jobs;
 pub mod monitoring;
 pub mod serialization;
 
 use std::str::FromStr;
 
-use crate::core::react::{BeatmapModifications, Beatmapset, NpsData, Rates};
+use crate::core::calc::bpm::calculate_bpm_stats;
+use crate::core::calc::nps::{calculate_nps_graph_with, NpsOptions};
+use crate::core::collection::add_to_collection;
+use crate::core::error::{AppError, AppResult, ErrorCode};
+use crate::core::react::{
+    BeatmapModifications, Beatmapset, DensityData, GeneratedBeatmap, NpsData, PatternData, Rates,
+};
 use monitoring::{emit_beatmap_changed, CurrentBeatmapWithRates};
 use serialization::serialize_beatmap;
 use tauri::{AppHandle, Manager};
@@ -18,7 +25,11 @@ pub async fn get_current_beatmap_from_state(app_handle: &AppHandle) -> Option<Be
     let current_data = current_beatmap.lock().await;
     current_data.beatmap_info.clone()
         .as_ref()
-        .map(|bwr| serialize_beatmap(&bwr, "Songs/"))
+        .map(|bwr| {
+            let mut beatmapset = serialize_beatmap(&bwr, "Songs/");
+            beatmapset.beatmaps[0].bpm = current_data.bpm.clone();
+            beatmapset
+        })
 }
 
 /// Return all rates for the current beatmap from state
@@ -35,6 +46,59 @@ pub async fn get_current_nps_data(app_handle: &AppHandle) -> Option<NpsData> {
     current_data.nps_data.clone()
 }
 
+/// Parse the .osu file of the current beatmap, if any
+pub async fn load_current_parsed_beatmap(
+    app_handle: &AppHandle,
+) -> AppResult<Option<rosu_map::Beatmap>> {
+    use rosu_map::Beatmap as RmBeatmap;
+    use crate::core::preferences;
+
+    let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
+    let beatmap_info = match current_beatmap.lock().await.beatmap_info.clone() {
+        Some(info) => info,
+        None => return Ok(None),
+    };
+
+    let songs_path = preferences::songs_path();
+    let osu_path = format!(
+        "{}/{}/{}",
+        songs_path, beatmap_info.location.folder, beatmap_info.location.filename
+    );
+    RmBeatmap::from_path(&osu_path)
+        .map(Some)
+        .map_err(|e| AppError::beatmap_file(&osu_path, e))
+}
+
+/// Recompute the NPS graph of the current beatmap with custom options
+pub async fn get_current_nps_data_with(
+    app_handle: &AppHandle,
+    options: &NpsOptions,
+) -> AppResult<Option<NpsData>> {
+    let Some(beatmap) = load_current_parsed_beatmap(app_handle).await? else {
+        return Ok(None);
+    };
+
+    let (nps_graph, drain_time) = calculate_nps_graph_with(&beatmap, options);
+    Ok(Some(NpsData {
+        nps_graph,
+        drain_time,
+    }))
+}
+
+/// Return density analysis for the current beatmap from state
+pub async fn get_current_density_data(app_handle: &AppHandle) -> Option<DensityData> {
+    let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
+    let current_data = current_beatmap.lock().await;
+    current_data.density_data.clone()
+}
+
+/// Return the pattern timeline for the current beatmap from state
+pub async fn get_current_pattern_data(app_handle: &AppHandle) -> Option<PatternData> {
+    let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
+    let current_data = current_beatmap.lock().await;
+    current_data.pattern_data.clone()
+}
+
 /// Return all current beatmap data (beatmap info, rates, and NPS)
 pub async fn get_current_beatmap_data(app_handle: &AppHandle) -> crate::core::internal::CurrentBeatmapData {
     let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
@@ -46,22 +110,21 @@ pub async fn get_current_beatmap_data(app_handle: &AppHandle) -> crate::core::in
 pub async fn apply_beatmap_modifications_core(
     app_handle: &AppHandle,
     modifications: BeatmapModifications,
-) -> Result<Beatmapset, String> {
+) -> AppResult<GeneratedBeatmap> {
     use std::fs;
     use rosu_map::Beatmap as RmBeatmap;
     use crate::core::preferences;
 
     // Get current beatmap from state
     let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
-    let current_data = current_beatmap.lock().await;
-
-    let beatmap_info = match &current_data.beatmap_info {
-        Some(info) => info,
-        None => return Err("No current beatmap loaded".to_string()),
+    // Work on a copy so the state isn't locked while the files are written
+    let beatmap_info = match &current_beatmap.lock().await.beatmap_info {
+        Some(info) => info.clone(),
+        None => return Err(AppError::no_beatmap()),
     };
 
     // Build the full path to the .osu file
-    let songs_path = preferences::load_config().songs_path;
+    let songs_path = preferences::songs_path();
     let osu_path = format!(
         "{}/{}/{}",
         songs_path, beatmap_info.location.folder, beatmap_info.location.filename
@@ -72,16 +135,17 @@ pub async fn apply_beatmap_modifications_core(
 
     // Validate that the file exists before attempting to read it
     if !std::path::Path::new(&osu_path).exists() {
-        return Err(format!("Beatmap file does not exist: {}", osu_path));
+        return Err(AppError::new(ErrorCode::FileNotFound, "Beatmap file does not exist")
+            .with_context(osu_path));
     }
 
     // Read the .osu file content
     let osu_content = fs::read_to_string(&osu_path)
-        .map_err(|e| format!("Failed to read beatmap file '{}': {}", osu_path, e))?;
+        .map_err(|e| AppError::io("read beatmap file", &osu_path, e))?;
 
     // Parse the beatmap
     let mut beatmap = RmBeatmap::from_str(&osu_content)
-        .map_err(|e| format!("Failed to parse beatmap '{}': {}", osu_path, e))?;
+        .map_err(|e| AppError::parse("beatmap", e).with_context(osu_path.clone()))?;
 
     // Apply modifications if provided
     let mut modifications_applied = Vec::new();
@@ -142,7 +206,7 @@ pub async fn apply_beatmap_modifications_core(
     }
 
     if modifications_applied.is_empty() {
-        return Err("No modifications to apply".to_string());
+        return Err(AppError::invalid_input("No modifications to apply"));
     }
 
     // Generate new filename with modifications
@@ -153,7 +217,8 @@ pub async fn apply_beatmap_modifications_core(
     let extension_pos = if let Some(pos) = original_filename.to_lowercase().rfind(osu_extension) {
         pos
     } else {
-        return Err(format!("Invalid beatmap filename (no .osu extension): {}", original_filename));
+        return Err(AppError::invalid_input("Invalid beatmap filename (no .osu extension)")
+            .with_context(original_filename.clone()));
     };
 
     let name_without_ext = &original_filename[..extension_pos];
@@ -174,7 +239,7 @@ pub async fn apply_beatmap_modifications_core(
 
     // Create the modified .osu content
     let modified_content = beatmap.encode_to_string()
-        .map_err(|e| format!("Failed to encode modified beatmap: {}", e))?;
+        .map_err(|e| AppError::internal(format!("Failed to encode modified beatmap: {}", e)))?;
 
     // Save the modified beatmap
     let new_path = format!("{}/{}/{}", songs_path, beatmap_info.location.folder, new_filename);
@@ -182,23 +247,36 @@ pub async fn apply_beatmap_modifications_core(
 
     // Ensure the directory exists
     let dir_path = std::path::Path::new(&new_path).parent()
-        .ok_or_else(|| format!("Invalid directory path for: {}", new_path))?;
+        .ok_or_else(|| AppError::invalid_input("Invalid directory path").with_context(new_path.clone()))?;
 
     if !dir_path.exists() {
-        return Err(format!("Beatmap directory does not exist: {}", dir_path.display()));
+        return Err(AppError::new(ErrorCode::FileNotFound, "Beatmap directory does not exist")
+            .with_context(dir_path.display().to_string()));
     }
 
-    fs::write(&new_path, modified_content)
-        .map_err(|e| format!("Failed to save modified beatmap '{}': {}", new_path, e))?;
+    fs::write(&new_path, &modified_content)
+        .map_err(|e| AppError::io("save modified beatmap", &new_path, e))?;
 
     println!("✅ Modified beatmap saved as: {}", new_path);
 
+    // Optionally make the generated map easy to find in song select
+    let config = preferences::load_config();
+    let collection = config.write_generated_to_collection.then(|| {
+        let md5 = format!("{:x}", md5::compute(modified_content.as_bytes()));
+        add_to_collection(&config.generated_collection_name, &[md5])
+    });
+
     // Return the modified beatmap info
-    let mut modified_beatmapset = serialize_beatmap(beatmap_info, &songs_path);
+    let mut modified_beatmapset = serialize_beatmap(&beatmap_info, &songs_path);
+    // Rate changes alter the BPM, describe the generated map rather than the original
+    modified_beatmapset.beatmaps[0].bpm = Some(calculate_bpm_stats(&beatmap));
     // Note: We don't update the filename in the response since it's just for display
     // The actual file has been saved with the new name
 
-    Ok(modified_beatmapset)
+    Ok(GeneratedBeatmap {
+        beatmapset: modified_beatmapset,
+        collection,
+    })
 }
 
 /// Build a demo beatmap for a given counter
@@ -215,7 +293,7 @@ pub fn build_demo_beatmap(counter: u32) -> Beatmapset {
 }
 
 /// Emit a demo beatmap event
-pub fn emit_demo_beatmap_core(app_handle: &AppHandle, counter: u32) -> Result<(), String> {
+pub fn emit_demo_beatmap_core(app_handle: &AppHandle, counter: u32) -> AppResult<()> {
     let beatmap = build_demo_beatmap(counter);
     emit_beatmap_changed(app_handle, beatmap);
     Ok(())
@@ -1,11 +1,13 @@
 use crate::core::beatmap::detection::detect_current_beatmap;
+use crate::core::beatmap::jobs::{record_finished_play, CalcJobs};
 use crate::core::beatmap::serialization::serialize_beatmap;
-use crate::core::calc::etterna::process_beatmap;
-use crate::core::calc::nps::calculate_nps_graph;
+use crate::core::calc::osu::LivePlay;
+use crate::core::connection::helpers::emit_heartbeat;
+use crate::core::connection::ConnectionDetails;
 use crate::core::internal::BeatmapWithRates;
 use crate::core::preferences;
-use crate::core::react::{NpsData, Rates};
-use minacalc_rs::Calc;
+use crate::core::profile::play::{live_score, read_finished_play, PlayTracker};
+use crate::core::react::{BpmStats, DensityData, NpsData, PatternData, Rates};
 use rosu_mem::process::Process;
 use rosu_memory_lib::reader::beatmap::common::BeatmapInfo;
 use rosu_memory_lib::reader::structs::State;
@@ -18,6 +20,9 @@ use crate::core::internal::CurrentBeatmapData;
 
 pub type CurrentBeatmapWithRates = Arc<Mutex<CurrentBeatmapData>>;
 
+/// Heartbeat and check that osu! is still running every this many ticks (2s)
+const PROCESS_CHECK_TICKS: u32 = 4;
+
 pub fn emit_beatmap_changed(app_handle: &AppHandle, beatmapset: crate::core::react::Beatmapset) {
     match app_handle.emit("beatmap-changed", beatmapset.clone()) {
         Ok(_) => {}
@@ -56,70 +61,169 @@ pub fn emit_nps_calculated(app_handle: &AppHandle, nps_data: &NpsData) {
     }
 }
 
+pub fn emit_density_calculated(app_handle: &AppHandle, density_data: &DensityData) {
+    println!(
+        "📤 Emitting density-calculated: {} columns",
+        density_data.column_nps.len()
+    );
+    if let Err(e) = app_handle.emit("density-calculated", density_data.clone()) {
+        eprintln!("Failed to emit density data: {}", e);
+    }
+}
+
+pub fn emit_patterns_calculated(app_handle: &AppHandle, pattern_data: &PatternData) {
+    println!(
+        "📤 Emitting patterns-calculated: {} segments",
+        pattern_data.segments.len()
+    );
+    if let Err(e) = app_handle.emit("patterns-calculated", pattern_data.clone()) {
+        eprintln!("Failed to emit pattern data: {}", e);
+    }
+}
+
+pub fn emit_bpm_calculated(app_handle: &AppHandle, bpm: &BpmStats) {
+    if let Err(e) = app_handle.emit("bpm-calculated", bpm.clone()) {
+        eprintln!("Failed to emit BPM stats: {}", e);
+    }
+}
+
 async fn process_detected_beatmap(
     app_handle: &AppHandle,
     current_beatmap: CurrentBeatmapWithRates,
-    calc: &Calc,
+    jobs: &mut CalcJobs,
     beatmap_info: BeatmapInfo,
 ) {
     // Émettre immédiatement la beatmap sans ratings (pour affichage rapide)
     // Use configured songs path from preferences
-    let songs_path = preferences::load_config().songs_path;
+    let songs_path = preferences::songs_path();
     let beatmapset = serialize_beatmap(&beatmap_info, &songs_path);
     emit_beatmap_changed(app_handle, beatmapset);
 
-    let current_beatmap_clone = current_beatmap.clone();
-    let app_handle_clone = app_handle.clone();
+    let osu_path = format!(
+        "{}/{}/{}",
+        songs_path, beatmap_info.location.folder, beatmap_info.location.filename
+    );
+    let md5 = beatmap_info.technical.md5.clone();
+
+    // Results of the previous beatmap no longer describe the current one
+    {
+        let mut current = current_beatmap.lock().await;
+        current.beatmap_info = Some(beatmap_info);
+        current.rates = Vec::new();
+        current.nps_data = None;
+        current.density_data = None;
+        current.pattern_data = None;
+        current.bpm = None;
+    }
+
+    jobs.start(app_handle.clone(), current_beatmap, md5, osu_path);
+}
 
+/// Read the result screen and rate the play in the background
+async fn process_finished_play(
+    app_handle: &AppHandle,
+    current_beatmap: &CurrentBeatmapWithRates,
+    process: &Process,
+    state: &mut State,
+    mods: u32,
+) {
+    let Some(beatmap_info) = current_beatmap.lock().await.beatmap_info.clone() else {
+        return;
+    };
+    let play = match read_finished_play(process, state, mods) {
+        Ok(play) => play,
+        Err(e) => {
+            eprintln!("⚠️  Play not recorded: {}", e);
+            return;
+        }
+    };
+
+    let songs_path = preferences::songs_path();
     let osu_path = format!(
         "{}/{}/{}",
         songs_path, beatmap_info.location.folder, beatmap_info.location.filename
     );
-    println!("🧮 Processing beatmap for rates & NPS: {}", osu_path);
-    let result = process_beatmap(
-        &calc,
-        &osu_path,
-        beatmap_info.stats.length as f64,
-        beatmap_info.stats.length as f64,
-    )
-    .await
-    .map_err(|e| eprintln!("Failed to calculate rates: {}", e));
-
-    if let Ok((rates, parsed_beatmap)) = result {
-        println!("✅ Rates calculated: {} entries", rates.len());
-        let (nps_graph, drain_time) = calculate_nps_graph(&parsed_beatmap);
-        println!(
-            "✅ NPS calculated: {} points, drain_time={}s",
-            nps_graph.len(),
-            drain_time
-        );
-        let nps_data = NpsData {
-            nps_graph,
-            drain_time,
-        };
+    record_finished_play(app_handle.clone(), beatmap_info, osu_path, play);
+}
 
-        emit_rates_calculated(&app_handle_clone, &rates);
-        emit_nps_calculated(&app_handle_clone, &nps_data);
+/// Emit the pp of the play in progress, parsing each chart once and following it object by object
+async fn process_live_play(
+    app_handle: &AppHandle,
+    current_beatmap: &CurrentBeatmapWithRates,
+    live_map: &mut Option<(String, rosu_pp::Beatmap)>,
+    live_play: &mut Option<LivePlay>,
+    process: &Process,
+    state: &mut State,
+    mods: u32,
+) {
+    let Some(beatmap_info) = current_beatmap.lock().await.beatmap_info.clone() else {
+        return;
+    };
+    let md5 = &beatmap_info.technical.md5;
+    if live_map.as_ref().map(|(hash, _)| hash) != Some(md5) {
+        let songs_path = preferences::songs_path();
+        let osu_path = format!(
+            "{}/{}/{}",
+            songs_path, beatmap_info.location.folder, beatmap_info.location.filename
+        );
+        match rosu_pp::Beatmap::from_path(&osu_path) {
+            Ok(map) => *live_map = Some((md5.clone(), map)),
+            Err(e) => {
+                eprintln!("⚠️  Failed to parse beatmap for live pp: {}", e);
+                return;
+            }
+        }
+    }
+    let Some((_, map)) = live_map.as_ref() else {
+        return;
+    };
 
-        let mut current = current_beatmap_clone.lock().await;
-        current.beatmap_info = Some(beatmap_info);
-        current.rates = rates;
-        current.nps_data = Some(nps_data);
+    let score = match live_score(process, state) {
+        Ok(score) => score,
+        Err(_) => return,
+    };
+    let is_current = |play: &LivePlay| &play.md5 == md5 && play.mods == mods;
+    if !live_play.as_ref().is_some_and(is_current) {
+        *live_play = Some(LivePlay::new(md5.clone(), map, mods));
+    }
+    let Some(performance) = live_play
+        .as_mut()
+        .and_then(|play| play.update(map, score))
+    else {
+        return;
+    };
+    if let Err(e) = app_handle.emit("live-performance", performance) {
+        eprintln!("Failed to emit live performance: {}", e);
     }
 }
 
+/// Follow the selected beatmap and plays until the osu! process exits
 pub async fn beatmap_monitoring_loop(
     app_handle: AppHandle,
     current_beatmap: CurrentBeatmapWithRates,
     state: &mut State,
     process: &Process,
-    calc: &Calc,
+    details: &mut ConnectionDetails,
 ) -> Result<(), Error> {
     let mut last_hash: Option<String> = None;
+    let mut play_tracker = PlayTracker::default();
+    let mut jobs = CalcJobs::default();
+    let mut live_map: Option<(String, rosu_pp::Beatmap)> = None;
+    let mut live_play: Option<LivePlay> = None;
+    let mut tick: u32 = 0;
 
     loop {
         // Monitor loop tick
         // println!("🔁 Monitoring tick");
+        if tick.is_multiple_of(PROCESS_CHECK_TICKS) {
+            if !details.heartbeat(process, state) {
+                println!("👋 osu! process exited");
+                return Ok(());
+            }
+            emit_heartbeat(&app_handle, details);
+        }
+        tick = tick.wrapping_add(1);
+
         if let Ok(current_hash) =
             rosu_memory_lib::reader::beatmap::stable::memory::md5(process, state)
         {
@@ -134,7 +238,7 @@ pub async fn beatmap_monitoring_loop(
                     process_detected_beatmap(
                         &app_handle,
                         current_beatmap.clone(),
-                        &calc,
+                        &mut jobs,
                         beatmap_info,
                     )
                     .await;
@@ -146,6 +250,23 @@ pub async fn beatmap_monitoring_loop(
             }
         }
 
+        if let Some(mods) = play_tracker.poll(process, state) {
+            process_finished_play(&app_handle, &current_beatmap, process, state, mods).await;
+        } else if let Some(mods) = play_tracker.playing_mods() {
+            process_live_play(
+                &app_handle,
+                &current_beatmap,
+                &mut live_map,
+                &mut live_play,
+                process,
+                state,
+                mods,
+            )
+            .await;
+        } else {
+            live_play = None;
+        }
+
         tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
     }
 }
@@ -1,8 +1,19 @@
+use crate::core::calc::bpm::calculate_bpm_stats;
 use crate::core::react::{BeatmapInfo as BeatmapInfoModel, Beatmapset};
 use base64::{engine::general_purpose, Engine as _};
+use rosu_map::section::hit_objects::HitObjectKind;
+use rosu_map::Beatmap as RmBeatmap;
 use rosu_memory_lib::reader::beatmap::common::BeatmapInfo;
 use std::fs;
 
+/// Read a cover image from disk as a base64 data URL
+fn read_cover(cover_path: &str) -> Option<String> {
+    fs::read(cover_path).ok().map(|image_data| {
+        let b64 = general_purpose::STANDARD.encode(&image_data);
+        format!("data:image/jpeg;base64,{}", b64)
+    })
+}
+
 pub fn serialize_beatmap(beatmap_info: &BeatmapInfo, song_folder: &str) -> Beatmapset {
     let mut beatmap = Beatmapset::default();
 
@@ -14,10 +25,7 @@ pub fn serialize_beatmap(beatmap_info: &BeatmapInfo, song_folder: &str) -> Beatm
         "{}/{}/{}",
         song_folder, beatmap_info.location.folder, beatmap_info.location.cover
     );
-    beatmap.cover_url = fs::read(&cover_path).ok().map(|image_data| {
-        let b64 = general_purpose::STANDARD.encode(&image_data);
-        format!("data:image/jpeg;base64,{}", b64)
-    });
+    beatmap.cover_url = read_cover(&cover_path);
 
     beatmap.beatmaps = vec![BeatmapInfoModel::default()];
     beatmap.beatmaps[0].beatmap_osu_id = beatmap_info.technical.id;
@@ -28,6 +36,45 @@ pub fn serialize_beatmap(beatmap_info: &BeatmapInfo, song_folder: &str) -> Beatm
     beatmap.beatmaps[0].od = beatmap_info.stats.od as f64;
     beatmap.beatmaps[0].hp = beatmap_info.stats.hp as f64;
     beatmap.beatmaps[0].ratings = vec![]; // Les ratings sont ajoutés après le calcul
+    // BPM needs the parsed .osu, callers fill it in from wherever it was parsed
+    beatmap.beatmaps[0].bpm = None;
+
+    beatmap
+}
+
+/// Build a Beatmapset from a parsed .osu file located in `folder` of the Songs folder
+pub fn serialize_parsed_beatmap(parsed: &RmBeatmap, song_folder: &str, folder: &str) -> Beatmapset {
+    let mut beatmap = Beatmapset::default();
+
+    beatmap.osu_id = Some(parsed.beatmap_set_id).filter(|id| *id > 0);
+    beatmap.title = parsed.title.clone();
+    beatmap.artist = parsed.artist.clone();
+    beatmap.creator = parsed.creator.clone();
+
+    if !parsed.background_file.is_empty() {
+        let cover_path = format!("{}/{}/{}", song_folder, folder, parsed.background_file);
+        beatmap.cover_url = read_cover(&cover_path);
+    }
+
+    let (mut circles, mut sliders, mut spinners) = (0, 0, 0);
+    for hit_object in &parsed.hit_objects {
+        match hit_object.kind {
+            HitObjectKind::Circle(_) => circles += 1,
+            HitObjectKind::Slider(_) | HitObjectKind::Hold(_) => sliders += 1,
+            HitObjectKind::Spinner(_) => spinners += 1,
+        }
+    }
+
+    beatmap.beatmaps = vec![BeatmapInfoModel::default()];
+    beatmap.beatmaps[0].beatmap_osu_id = parsed.beatmap_id;
+    beatmap.beatmaps[0].name = parsed.version.clone();
+    beatmap.beatmaps[0].count_circles = circles;
+    beatmap.beatmaps[0].count_sliders = sliders;
+    beatmap.beatmaps[0].count_spinners = spinners;
+    beatmap.beatmaps[0].od = parsed.overall_difficulty as f64;
+    beatmap.beatmaps[0].hp = parsed.hp_drain_rate as f64;
+    beatmap.beatmaps[0].ratings = vec![];
+    beatmap.beatmaps[0].bpm = Some(calculate_bpm_stats(parsed));
 
     beatmap
 }
@@ -0,0 +1,79 @@
+use crate::core::react::{BpmPoint, BpmStats};
+use rosu_map::Beatmap as RmBeatmap;
+use std::collections::HashMap;
+
+fn bpm_of(beat_len: f64) -> Option<f64> {
+    (beat_len > 0.0).then(|| 60000.0 / beat_len)
+}
+
+/// BPM range, dominant BPM by note count and BPM changes over time
+pub fn calculate_bpm_stats(beatmap: &RmBeatmap) -> BpmStats {
+    let timing_points: Vec<(f64, f64)> = beatmap
+        .control_points
+        .timing_points
+        .iter()
+        .filter_map(|tp| bpm_of(tp.beat_len).map(|bpm| (tp.time, bpm)))
+        .collect();
+    if timing_points.is_empty() {
+        return BpmStats::default();
+    }
+
+    // Notes before the first timing point use its BPM
+    let mut notes_per_point = vec![0usize; timing_points.len()];
+    let mut point_idx = 0;
+    for hit_object in &beatmap.hit_objects {
+        while point_idx + 1 < timing_points.len()
+            && timing_points[point_idx + 1].0 <= hit_object.start_time
+        {
+            point_idx += 1;
+        }
+        notes_per_point[point_idx] += 1;
+    }
+
+    // Rounded to 0.001 BPM so equal BPMs from different timing points add up
+    let mut notes_per_bpm: HashMap<i64, usize> = HashMap::new();
+    for ((_, bpm), notes) in timing_points.iter().zip(&notes_per_point) {
+        *notes_per_bpm
+            .entry((bpm * 1000.0).round() as i64)
+            .or_insert(0) += notes;
+    }
+    let dominant = notes_per_bpm
+        .into_iter()
+        .max_by_key(|(bpm, notes)| (*notes, *bpm))
+        .map(|(bpm, _)| bpm as f64 / 1000.0)
+        .unwrap_or(timing_points[0].1);
+
+    // Range over the timing points that carry notes, ignoring gimmick sections
+    let playable: Vec<f64> = timing_points
+        .iter()
+        .zip(&notes_per_point)
+        .filter(|(_, notes)| **notes > 0)
+        .map(|((_, bpm), _)| *bpm)
+        .collect();
+    let range: Vec<f64> = if playable.is_empty() {
+        timing_points.iter().map(|(_, bpm)| *bpm).collect()
+    } else {
+        playable
+    };
+
+    let mut series: Vec<BpmPoint> = Vec::new();
+    for (time, bpm) in &timing_points {
+        if series
+            .last()
+            .is_some_and(|point| (point.bpm - bpm).abs() < 0.001)
+        {
+            continue;
+        }
+        series.push(BpmPoint {
+            time: time / 1000.0,
+            bpm: *bpm,
+        });
+    }
+
+    BpmStats {
+        min: range.iter().copied().fold(f64::INFINITY, f64::min),
+        max: range.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        dominant,
+        series,
+    }
+}
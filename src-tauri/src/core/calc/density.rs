@@ -0,0 +1,133 @@
+use super::notes::{group_rows, key_count, mania_notes, ManiaNote};
+use crate::core::react::{DensityData, PeakDensity};
+use rosu_map::Beatmap as RmBeatmap;
+
+/// Number of sections in the density curves, matching the NPS graph
+const SECTIONS: usize = 100;
+/// Sliding windows (seconds) for the peak sustained density
+const PEAK_WINDOWS: [f64; 4] = [1.0, 5.0, 10.0, 30.0];
+
+pub fn calculate_density(beatmap: &RmBeatmap) -> DensityData {
+    let keys = key_count(beatmap);
+    let notes = mania_notes(beatmap);
+    let rows = group_rows(&notes);
+
+    let first = notes.first().map(|note| note.start).unwrap_or(0.0);
+    let last = notes.last().map(|note| note.start).unwrap_or(0.0);
+    let drain_ms = last - first;
+
+    let mut column_nps = vec![vec![0.0; SECTIONS]; keys];
+    let mut ln_coverage = vec![0.0; SECTIONS];
+    if drain_ms > 0.0 {
+        let section_seconds = drain_ms / 1000.0 / SECTIONS as f64;
+        for note in &notes {
+            let section = (((note.start - first) / drain_ms) * SECTIONS as f64).floor() as usize;
+            if section < SECTIONS {
+                column_nps[note.column][section] += 1.0 / section_seconds;
+            }
+        }
+        ln_coverage = long_note_coverage(&notes, first, drain_ms, keys);
+    }
+
+    let (left_hand_nps, right_hand_nps) = hand_nps(&column_nps);
+    let (column_jacks, chord_distribution) = row_stats(&rows, keys);
+
+    DensityData {
+        key_count: keys,
+        drain_time: drain_ms / 1000.0,
+        left_hand_nps,
+        right_hand_nps,
+        column_nps,
+        chord_distribution,
+        jack_count: column_jacks.iter().sum(),
+        column_jacks,
+        ln_coverage,
+        peak_density: PEAK_WINDOWS
+            .iter()
+            .map(|window| peak_density(&notes, *window, first))
+            .collect(),
+    }
+}
+
+/// Split the column curves between hands, the middle column of odd key counts counting half for each
+fn hand_nps(column_nps: &[Vec<f64>]) -> (Vec<f64>, Vec<f64>) {
+    let keys = column_nps.len();
+    let mut left = vec![0.0; SECTIONS];
+    let mut right = vec![0.0; SECTIONS];
+    for (column, curve) in column_nps.iter().enumerate() {
+        let left_share = if keys % 2 == 1 && column == keys / 2 {
+            0.5
+        } else if column < keys / 2 {
+            1.0
+        } else {
+            0.0
+        };
+        for (section, nps) in curve.iter().enumerate() {
+            left[section] += nps * left_share;
+            right[section] += nps * (1.0 - left_share);
+        }
+    }
+    (left, right)
+}
+
+/// Jacks per column and chord size distribution
+fn row_stats(rows: &[Vec<ManiaNote>], keys: usize) -> (Vec<u32>, Vec<u32>) {
+    let mut column_jacks = vec![0; keys];
+    let mut chord_distribution = vec![0; keys];
+    let mut previous: &[ManiaNote] = &[];
+    for row in rows {
+        if let Some(count) = chord_distribution.get_mut(row.len().saturating_sub(1)) {
+            *count += 1;
+        }
+        for note in row {
+            if previous.iter().any(|other| other.column == note.column) {
+                column_jacks[note.column] += 1;
+            }
+        }
+        previous = row;
+    }
+    (column_jacks, chord_distribution)
+}
+
+fn long_note_coverage(notes: &[ManiaNote], first: f64, drain_ms: f64, keys: usize) -> Vec<f64> {
+    let section_ms = drain_ms / SECTIONS as f64;
+    let mut held = vec![0.0; SECTIONS];
+    for note in notes {
+        let Some(end) = note.end else { continue };
+        let start = note.start - first;
+        let end = (end - first).min(drain_ms);
+        let first_section = (start / section_ms).floor().max(0.0) as usize;
+        for (section, held_ms) in held.iter_mut().enumerate().skip(first_section) {
+            let section_start = section as f64 * section_ms;
+            if section_start >= end {
+                break;
+            }
+            let overlap = end.min(section_start + section_ms) - start.max(section_start);
+            *held_ms += overlap.max(0.0);
+        }
+    }
+    held.iter()
+        .map(|held_ms| held_ms / (section_ms * keys as f64))
+        .collect()
+}
+
+/// Highest note count over a sliding window of `window` seconds
+fn peak_density(notes: &[ManiaNote], window: f64, first: f64) -> PeakDensity {
+    let window_ms = window * 1000.0;
+    let mut best = (0, first);
+    let mut start_idx = 0;
+    for (end_idx, note) in notes.iter().enumerate() {
+        while note.start - notes[start_idx].start >= window_ms {
+            start_idx += 1;
+        }
+        let count = end_idx - start_idx + 1;
+        if count > best.0 {
+            best = (count, notes[start_idx].start);
+        }
+    }
+    PeakDensity {
+        window,
+        nps: best.0 as f64 / window,
+        start_time: (best.1 - first) / 1000.0,
+    }
+}
@@ -0,0 +1,50 @@
+use serde::Serialize;
+use std::fmt;
+
+/// Why a beatmap could not be rated
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CalcError {
+    /// The .osu file does not exist or cannot be read
+    MissingFile { path: String, message: String },
+    /// The .osu file is not a valid beatmap
+    Parse { path: String, message: String },
+    /// A difficulty calculator rejected the chart
+    Calc { message: String },
+    /// The calculation was cancelled because another beatmap was selected
+    Cancelled,
+}
+
+impl CalcError {
+    pub fn calc(error: impl fmt::Display) -> Self {
+        CalcError::Calc {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalcError::MissingFile { path, message } => {
+                write!(f, "Failed to read beatmap '{}': {}", path, message)
+            }
+            CalcError::Parse { path, message } => {
+                write!(f, "Failed to parse beatmap '{}': {}", path, message)
+            }
+            CalcError::Calc { message } => write!(f, "Failed to calculate ratings: {}", message),
+            CalcError::Cancelled => write!(f, "Calculation cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+/// Payload of the `calc-error` event
+#[derive(Debug, Clone, Serialize)]
+pub struct CalcFailure {
+    pub md5: String,
+    pub error: CalcError,
+    /// Human readable description of `error`
+    pub message: String,
+}
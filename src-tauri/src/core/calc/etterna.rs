@@ -1,18 +1,26 @@
-use super::osu::get_star_rating;
+use super::bpm::calculate_bpm_stats;
+use super::error::CalcError;
+use super::osu::{create_osu_rating, process_other_mode};
+use super::timing::{calculate_timing, ChartTiming};
 use crate::core::react::{ManiaRating, ModeRating, Rates, Rating};
 use anyhow::Result;
 use minacalc_rs::{hashmap::HashMapCalcExt, Calc, OsuCalcExt, Ssr};
+use rosu_map::section::general::GameMode;
 use rosu_map::Beatmap as RmBeatmap;
 use std::fs;
 use std::str::FromStr;
-
-pub struct RatesMaker {
-    pub rate: String,
-    pub drain_time: f64,
-    pub total_time: f64,
-    pub bpm: f32,
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Everything needed to rate one rate of a chart, the chart itself is shared between rates
+pub struct RatesMaker<'a> {
+    pub rate: f64,
+    /// Chart timing at 1.0x
+    pub timing: ChartTiming,
+    /// Dominant BPM at 1.0x
+    pub bpm: f64,
     pub skillset_scores: Ssr,
-    pub osu_map: RmBeatmap,
+    /// Unmodified chart, rated by rosu-pp at the rate's clock rate
+    pub pp_map: &'a rosu_pp::Beatmap,
 }
 
 #[derive(Clone)]
@@ -26,47 +34,39 @@ pub struct Proportion {
     pub technical: f64,
 }
 
-pub async fn rates_from_skillset_scores(make_rates: &mut RatesMaker) -> Result<Rates> {
+/// Rates of one rate, no rated .osu is generated so it has no hash
+pub fn rates_from_skillset_scores(make_rates: &RatesMaker) -> Rates {
     let rate_data = calculate_rate_data(make_rates);
     let proportions = calculate_proportions(make_rates);
-    let osu_map = encode_beatmap_to_string(make_rates);
-    let osu_hash = generate_beatmap_hash(&osu_map);
-    let ratings = create_all_ratings(make_rates, &proportions, &osu_map);
+    let ratings = create_all_ratings(make_rates, &proportions);
 
-    let rates = Rates {
+    Rates {
         id: None,
-        osu_hash: Some(osu_hash),
         centirate: rate_data.centirate,
-        drain_time: rate_data.drain_time as f64,
-        total_time: rate_data.total_time as f64,
-        bpm: rate_data.bpm as f64,
+        drain_time: rate_data.drain_time,
+        total_time: rate_data.total_time,
+        bpm: rate_data.bpm,
         rating: ratings,
-    };
-
-    Ok(rates)
+    }
 }
 
 #[derive(Debug)]
 struct RateData {
     centirate: i32,
-    drain_time: i32,
-    total_time: i32,
-    bpm: f32,
+    drain_time: f64,
+    total_time: f64,
+    bpm: f64,
 }
 
 fn calculate_rate_data(make_rates: &RatesMaker) -> RateData {
-    let rate = make_rates.rate.parse::<f64>().unwrap();
-    let centirate = rate * 100.0;
-    let proportion_rate = 100.0 / centirate;
-    let drain_time = make_rates.drain_time * proportion_rate;
-    let total_time = make_rates.total_time * proportion_rate;
-    let bpm = make_rates.bpm as f64 * rate;
+    let rate = make_rates.rate;
+    let timing = make_rates.timing.at_rate(rate);
 
     RateData {
-        centirate: centirate as i32,
-        drain_time: drain_time as i32,
-        total_time: total_time as i32,
-        bpm: bpm as f32,
+        centirate: (rate * 100.0).round() as i32,
+        drain_time: timing.drain_time,
+        total_time: timing.total_length,
+        bpm: make_rates.bpm * rate,
     }
 }
 
@@ -84,26 +84,9 @@ fn calculate_proportions(make_rates: &RatesMaker) -> Proportion {
     }
 }
 
-fn encode_beatmap_to_string(make_rates: &RatesMaker) -> String {
-    make_rates.osu_map.clone().encode_to_string().unwrap()
-}
-
-fn generate_beatmap_hash(osu_map: &str) -> String {
-    hash_md5(osu_map).unwrap()
-}
-
-fn hash_md5(content: &str) -> Result<String> {
-    let digest = md5::compute(content.as_bytes());
-    Ok(format!("{:x}", digest))
-}
-
-fn create_all_ratings(
-    make_rates: &RatesMaker,
-    proportions: &Proportion,
-    osu_map: &str,
-) -> Vec<Rating> {
+fn create_all_ratings(make_rates: &RatesMaker, proportions: &Proportion) -> Vec<Rating> {
     let etterna_rating = create_etterna_rating(make_rates);
-    let osu_rating = create_osu_rating(osu_map, proportions);
+    let osu_rating = create_osu_rating(make_rates.pp_map, make_rates.rate, proportions);
 
     vec![etterna_rating, osu_rating]
 }
@@ -114,6 +97,7 @@ fn create_etterna_rating(make_rates: &RatesMaker) -> Rating {
         rates_id: None,
         rating: make_rates.skillset_scores.overall as f64,
         rating_type: "etterna".to_string(),
+        pp: Vec::new(),
         mode_rating: ModeRating::Mania {
             Mania: ManiaRating {
                 id: None,
@@ -129,17 +113,13 @@ fn create_etterna_rating(make_rates: &RatesMaker) -> Rating {
     }
 }
 
-fn create_osu_rating(osu_map: &str, proportions: &Proportion) -> Rating {
-    let stars = get_star_rating(osu_map);
-    rating_new("osu".to_string(), stars, proportions.clone())
-}
-
 pub fn rating_new(rating_type: String, rating: f64, proportion: Proportion) -> Rating {
     Rating {
         id: None,
         rates_id: None,
         rating: rating as f64,
         rating_type,
+        pp: Vec::new(),
         mode_rating: ModeRating::Mania {
             Mania: ManiaRating {
                 id: None,
@@ -156,82 +136,67 @@ pub fn rating_new(rating_type: String, rating: f64, proportion: Proportion) -> R
 }
 
 
-pub(crate) async fn calc_bpm(rm_beatmap: &RmBeatmap) -> f32 {
-    let timing_points = &rm_beatmap.control_points.timing_points;
-    if timing_points.is_empty() {
-        return 0.0;
-    }
-
-    // Fin de la map: dernier hit object si dispo, sinon dernier timing point
-    let map_end_time = rm_beatmap
-        .hit_objects
-        .last()
-        .map(|o| o.start_time)
-        .unwrap_or_else(|| timing_points.last().map(|tp| tp.time).unwrap_or(0.0));
-
-    // Calculer toutes les durées en une passe
-    let mut durations: Vec<f64> = Vec::with_capacity(timing_points.len());
-    for i in 0..timing_points.len() {
-        let current_time = timing_points[i].time;
-        let next_time = if i + 1 < timing_points.len() {
-            timing_points[i + 1].time
-        } else {
-            map_end_time
-        };
-        let duration = (next_time - current_time).max(0.0);
-        durations.push(duration);
-    }
-
-    // Trouver l'index du timing point avec la durée max
-    let mut max_idx = 0usize;
-    let mut max_duration = durations[0];
-    for (idx, &dur) in durations.iter().enumerate().skip(1) {
-        if dur > max_duration {
-            max_duration = dur;
-            max_idx = idx;
-        }
-    }
-    println!("timings_points: {:?}", timing_points);
-
-    let tp = &timing_points[max_idx];
-    println!(
-        "🧮 Longest timing point idx={}, time={}, duration={}",
-        max_idx, tp.time, max_duration
-    );
-    
-    if tp.beat_len > 0.0 {
-        (60000.0 / tp.beat_len) as f32
-    } else {
-        0.0
-    }
+pub(crate) fn process_beatmap(
+    calc: &Calc,
+    osu_path: &str,
+) -> Result<(Vec<Rates>, RmBeatmap), CalcError> {
+    process_beatmap_cancellable(calc, osu_path, &AtomicBool::new(false))
 }
 
-pub(crate) async fn process_beatmap(
+/// Rate every rate of a beatmap, giving up between stages once `cancelled` is set
+pub(crate) fn process_beatmap_cancellable(
     calc: &Calc,
     osu_path: &str,
-    seconds_drain: f64,
-    seconds_total: f64,
-) -> Result<(Vec<Rates>, RmBeatmap)> {
-    let osu_map = fs::read_to_string(osu_path).unwrap();
-    let parsed_beatmap = RmBeatmap::from_str(&osu_map).unwrap();
+    cancelled: &AtomicBool,
+) -> Result<(Vec<Rates>, RmBeatmap), CalcError> {
+    let check_cancelled = || {
+        if cancelled.load(Ordering::Relaxed) {
+            Err(CalcError::Cancelled)
+        } else {
+            Ok(())
+        }
+    };
+
+    let osu_map = fs::read_to_string(osu_path).map_err(|e| CalcError::MissingFile {
+        path: osu_path.to_string(),
+        message: e.to_string(),
+    })?;
+    let parse_error = |message: String| CalcError::Parse {
+        path: osu_path.to_string(),
+        message,
+    };
+    let parsed_beatmap = RmBeatmap::from_str(&osu_map).map_err(|e| parse_error(e.to_string()))?;
+    check_cancelled()?;
+    let bpm = calculate_bpm_stats(&parsed_beatmap).dominant;
+    let timing = calculate_timing(&parsed_beatmap);
+
+    // MSD only makes sense for osu!mania charts
+    if parsed_beatmap.mode != GameMode::Mania {
+        let rates = process_other_mode(&osu_map, timing, bpm).map_err(CalcError::calc)?;
+        return Ok((rates, parsed_beatmap));
+    }
 
     let skillset_scores = calc
-        .calculate_msd_from_string(osu_map.clone())?
-        .as_hashmap()?;
+        .calculate_msd_from_string(osu_map.clone())
+        .and_then(|scores| scores.as_hashmap())
+        .map_err(CalcError::calc)?;
+    check_cancelled()?;
 
+    // Every rate is rated from this single parse through the clock rate
+    let pp_map = rosu_pp::Beatmap::from_str(&osu_map).map_err(|e| parse_error(e.to_string()))?;
     let mut all_rates = vec![];
     for (rate_key, skillset_scores) in skillset_scores {
-        let mut rates_maker = RatesMaker {
-            skillset_scores: skillset_scores,
-            osu_map: parsed_beatmap.clone(),
-            rate: rate_key.clone(),
-            drain_time: seconds_drain,
-            total_time: seconds_total,
-            bpm: calc_bpm(&parsed_beatmap).await,
+        check_cancelled()?;
+        let rates_maker = RatesMaker {
+            rate: rate_key.parse().map_err(CalcError::calc)?,
+            timing,
+            bpm,
+            skillset_scores,
+            pp_map: &pp_map,
         };
-        let rates: Rates = rates_from_skillset_scores(&mut rates_maker).await.unwrap();
-        all_rates.push(rates);
+        all_rates.push(rates_from_skillset_scores(&rates_maker));
     }
+    all_rates.sort_by_key(|rates| rates.centirate);
 
     Ok((all_rates, parsed_beatmap))
 }
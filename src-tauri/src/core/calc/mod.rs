@@ -1,4 +1,12 @@
+pub mod bpm;
+pub mod density;
+pub mod error;
 pub mod etterna;
+pub mod notes;
 pub mod nps;
 pub mod osu;
+pub mod patterns;
 pub mod sunnyxxy;
+pub mod timeline;
+pub mod timing;
+pub mod wife;
@@ -0,0 +1,63 @@
+use rosu_map::section::hit_objects::HitObjectKind;
+use rosu_map::Beatmap as RmBeatmap;
+
+/// A single osu!mania note, times in milliseconds
+#[derive(Debug, Clone, Copy)]
+pub struct ManiaNote {
+    pub column: usize,
+    pub start: f64,
+    /// End time of the long note, if any
+    pub end: Option<f64>,
+}
+
+/// Notes closer than this (ms) belong to the same row
+const ROW_TOLERANCE_MS: f64 = 1.0;
+
+/// Key count of an osu!mania beatmap
+pub fn key_count(beatmap: &RmBeatmap) -> usize {
+    (beatmap.circle_size.round() as usize).max(1)
+}
+
+fn column_of(x: f32, key_count: usize) -> usize {
+    ((x as f64 * key_count as f64 / 512.0).floor().max(0.0) as usize).min(key_count - 1)
+}
+
+/// Notes of an osu!mania beatmap sorted by time, then column
+pub fn mania_notes(beatmap: &RmBeatmap) -> Vec<ManiaNote> {
+    let keys = key_count(beatmap);
+    let mut notes: Vec<ManiaNote> = beatmap
+        .hit_objects
+        .iter()
+        .filter_map(|hit_object| match &hit_object.kind {
+            HitObjectKind::Circle(circle) => Some(ManiaNote {
+                column: column_of(circle.pos.x, keys),
+                start: hit_object.start_time,
+                end: None,
+            }),
+            HitObjectKind::Hold(hold) => Some(ManiaNote {
+                column: column_of(hold.pos_x, keys),
+                start: hit_object.start_time,
+                end: Some(hit_object.start_time + hold.duration),
+            }),
+            _ => None,
+        })
+        .collect();
+    notes.sort_by(|a, b| a.start.total_cmp(&b.start).then(a.column.cmp(&b.column)));
+    notes
+}
+
+/// Group time-sorted notes starting together into rows (chords)
+pub fn group_rows(notes: &[ManiaNote]) -> Vec<Vec<ManiaNote>> {
+    let mut rows: Vec<Vec<ManiaNote>> = Vec::new();
+    let mut row_time = f64::NEG_INFINITY;
+    for note in notes {
+        if note.start - row_time > ROW_TOLERANCE_MS {
+            rows.push(Vec::new());
+            row_time = note.start;
+        }
+        if let Some(row) = rows.last_mut() {
+            row.push(*note);
+        }
+    }
+    rows
+}
@@ -1,34 +1,92 @@
 use rosu_map::Beatmap as RmBeatmap;
+use serde::Deserialize;
+
+const DEFAULT_BUCKETS: usize = 100;
+/// Options come from the frontend, bound them so a graph can't allocate without limit
+const MAX_BUCKETS: usize = 10_000;
+const MIN_WINDOW_SECONDS: f64 = 0.05;
+
+/// Resolution, smoothing and rate of an NPS graph
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NpsOptions {
+    /// Number of sections, ignored when `window_seconds` is set
+    pub buckets: Option<usize>,
+    /// Fixed section length in seconds (of scaled time)
+    pub window_seconds: Option<f64>,
+    /// Moving average width in sections, 1 or less disables smoothing
+    pub smoothing: Option<usize>,
+    /// Playback rate, e.g. 1.3 for a 1.3x graph
+    pub rate: Option<f64>,
+}
 
 pub fn calculate_nps_graph(beatmap: &RmBeatmap) -> (Vec<f64>, f64) {
+    calculate_nps_graph_with(beatmap, &NpsOptions::default())
+}
+
+/// NPS graph and drain time in seconds, both in scaled time when a rate is given
+pub fn calculate_nps_graph_with(beatmap: &RmBeatmap, options: &NpsOptions) -> (Vec<f64>, f64) {
+    let rate = options.rate.filter(|rate| *rate > 0.0).unwrap_or(1.0);
+    let default_buckets = options
+        .buckets
+        .unwrap_or(DEFAULT_BUCKETS)
+        .clamp(1, MAX_BUCKETS);
+
     if beatmap.hit_objects.is_empty() {
-        return (vec![0.0; 100], 0.0);
+        return (vec![0.0; default_buckets], 0.0);
     }
 
-    let first_note_time = beatmap.hit_objects.first().unwrap().start_time as f64 / 1000.0;
-    let last_note_time = beatmap.hit_objects.last().unwrap().start_time as f64 / 1000.0;
+    let first_note_time = beatmap.hit_objects.first().unwrap().start_time / 1000.0 / rate;
+    let last_note_time = beatmap.hit_objects.last().unwrap().start_time / 1000.0 / rate;
 
     let drain_time = last_note_time - first_note_time;
 
     if drain_time <= 0.0 {
-        return (vec![0.0; 100], 0.0);
+        return (vec![0.0; default_buckets], 0.0);
     }
 
-    let section_duration = drain_time / 100.0;
-    let mut nps_data = vec![0.0; 100];
+    // Long charts widen the window rather than exceed `MAX_BUCKETS`
+    let window = options
+        .window_seconds
+        .filter(|window| *window > 0.0)
+        .map(|window| {
+            window
+                .max(MIN_WINDOW_SECONDS)
+                .max(drain_time / MAX_BUCKETS as f64)
+        });
+    let buckets = match window {
+        Some(window) => ((drain_time / window).ceil() as usize).clamp(1, MAX_BUCKETS),
+        None => default_buckets,
+    };
+    let section_duration = window.unwrap_or(drain_time / buckets as f64);
+    let mut nps_data = vec![0.0; buckets];
 
     for hit_object in &beatmap.hit_objects {
-        let time = hit_object.start_time as f64 / 1000.0 - first_note_time;
-        let section_index = ((time / drain_time) * 100.0).floor() as usize;
-
-        if section_index < 100 {
-            nps_data[section_index] += 1.0;
-        }
+        let time = hit_object.start_time / 1000.0 / rate - first_note_time;
+        // The last note belongs to the final section
+        let section_index = ((time / section_duration).floor() as usize).min(buckets - 1);
+        nps_data[section_index] += 1.0;
     }
 
     for nps in &mut nps_data {
-        *nps = *nps / section_duration;
+        *nps /= section_duration;
+    }
+
+    let smoothing = options.smoothing.unwrap_or(1).min(buckets);
+    if smoothing > 1 {
+        nps_data = smooth(&nps_data, smoothing);
     }
 
     (nps_data, drain_time)
 }
+
+/// Centered moving average over `width` sections
+fn smooth(values: &[f64], width: usize) -> Vec<f64> {
+    let half = width / 2;
+    (0..values.len())
+        .map(|index| {
+            let start = index.saturating_sub(half);
+            let end = (index + width - half).min(values.len());
+            values[start..end].iter().sum::<f64>() / (end - start) as f64
+        })
+        .collect()
+}
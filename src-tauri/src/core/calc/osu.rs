@@ -1,18 +1,169 @@
 use super::etterna::{rating_new, Proportion};
-use crate::core::react::Rating;
+use super::timing::ChartTiming;
+use crate::core::react::{
+    CtbRating, LivePerformance, ModeRating, PpValue, Rates, Rating, StdRating, TaikoRating,
+};
+use crate::core::replay::judgement::clock_rate;
+use anyhow::Result;
+use rosu_pp::any::{DifficultyAttributes, PerformanceAttributes, ScoreState};
+use rosu_pp::GradualPerformance;
 use std::str::FromStr;
 
-pub fn create_osu_rating(osu_map: &str, proportions: &Proportion) -> Rating {
-    let stars = get_star_rating(osu_map);
-    rating_new("osu".to_string(), stars, proportions.clone())
+/// Clock rates rated for every beatmap, matching MinaCalc's 0.7x to 2.0x range
+const CLOCK_RATES: [f64; 14] = [
+    0.7, 0.8, 0.9, 1.0, 1.1, 1.2, 1.3, 1.4, 1.5, 1.6, 1.7, 1.8, 1.9, 2.0,
+];
+/// Accuracies (in percent) the pp of every rate is calculated at
+const PP_ACCURACIES: [f64; 6] = [95.0, 96.0, 97.0, 98.0, 99.0, 100.0];
+
+/// Star rating and pp of a rate, split across skillsets like the MSD
+pub fn create_osu_rating(
+    map: &rosu_pp::Beatmap,
+    clock_rate: f64,
+    proportions: &Proportion,
+) -> Rating {
+    let attrs = rosu_pp::Difficulty::new()
+        .clock_rate(clock_rate)
+        .calculate(map);
+    let mut rating = rating_new("osu".to_string(), attrs.stars(), proportions.clone());
+    rating.pp = pp_at_accuracies(&attrs);
+    rating
+}
+
+/// pp of a full combo at each of `PP_ACCURACIES`, attributes already include the clock rate
+pub fn pp_at_accuracies(attrs: &DifficultyAttributes) -> Vec<PpValue> {
+    PP_ACCURACIES
+        .iter()
+        .map(|accuracy| PpValue {
+            accuracy: *accuracy,
+            pp: attrs
+                .clone()
+                .performance()
+                .accuracy(*accuracy)
+                .calculate()
+                .pp(),
+        })
+        .collect()
+}
+
+/// pp of a play in progress, fed only the objects passed since the last update
+pub struct LivePlay {
+    /// md5 of the beatmap being played
+    pub md5: String,
+    pub mods: u32,
+    mode: rosu_pp::model::mode::GameMode,
+    gradual: GradualPerformance,
+    passed_objects: u32,
+    attrs: Option<PerformanceAttributes>,
 }
 
-pub fn get_star_rating(osu_map: &str) -> f64 {
-    match rosu_pp::Beatmap::from_str(osu_map) {
-        Ok(map) => {
-            let diff_attrs = rosu_pp::Difficulty::new().calculate(&map);
-            diff_attrs.stars()
+impl LivePlay {
+    pub fn new(md5: String, map: &rosu_pp::Beatmap, mods: u32) -> Self {
+        Self {
+            md5,
+            mods,
+            mode: map.mode,
+            gradual: GradualPerformance::new(rosu_pp::Difficulty::new().mods(mods), map),
+            passed_objects: 0,
+            attrs: None,
         }
-        Err(_) => 0.0,
     }
+
+    /// Process the judgements so far, `None` until the first object is judged
+    pub fn update(&mut self, map: &rosu_pp::Beatmap, score: ScoreState) -> Option<LivePerformance> {
+        let passed_objects = score.total_hits(self.mode);
+        // The score only goes back when the map is retried
+        if passed_objects < self.passed_objects {
+            *self = Self::new(self.md5.clone(), map, self.mods);
+        }
+        let new_objects = (passed_objects - self.passed_objects) as usize;
+        if new_objects > 0 {
+            // `nth` is zero-based: n = 0 processes one object
+            self.attrs = self
+                .gradual
+                .nth(score, new_objects - 1)
+                .or(self.attrs.take());
+            self.passed_objects = passed_objects;
+        }
+        let attrs = self.attrs.as_ref()?;
+
+        Some(LivePerformance {
+            centirate: (clock_rate(self.mods) as f64 * 100.0).round() as i32,
+            stars: attrs.stars(),
+            pp: attrs.pp(),
+            passed_objects,
+        })
+    }
+}
+
+/// rosu-pp rating with the attributes of the map's own mode, `None` for osu!mania
+pub fn create_mode_rating(map: &rosu_pp::Beatmap, clock_rate: f64) -> Option<Rating> {
+    let attrs = rosu_pp::Difficulty::new()
+        .clock_rate(clock_rate)
+        .calculate(map);
+    let stars = attrs.stars();
+    let pp = pp_at_accuracies(&attrs);
+
+    let mode_rating = match attrs {
+        DifficultyAttributes::Osu(attrs) => ModeRating::Std {
+            Std: StdRating {
+                aim: attrs.aim,
+                speed: attrs.speed,
+                flashlight: attrs.flashlight,
+                slider_factor: attrs.slider_factor,
+                speed_note_count: attrs.speed_note_count,
+                ar: attrs.ar,
+                max_combo: attrs.max_combo(),
+            },
+        },
+        DifficultyAttributes::Taiko(attrs) => ModeRating::Taiko {
+            Taiko: TaikoRating {
+                stamina: attrs.stamina,
+                rhythm: attrs.rhythm,
+                color: attrs.color,
+                reading: attrs.reading,
+                max_combo: attrs.max_combo(),
+            },
+        },
+        DifficultyAttributes::Catch(attrs) => ModeRating::Ctb {
+            Ctb: CtbRating {
+                ar: attrs.ar,
+                fruits: attrs.n_fruits,
+                droplets: attrs.n_droplets,
+                tiny_droplets: attrs.n_tiny_droplets,
+                max_combo: attrs.max_combo(),
+            },
+        },
+        DifficultyAttributes::Mania(_) => return None,
+    };
+
+    Some(Rating {
+        id: None,
+        rates_id: None,
+        rating: stars,
+        rating_type: "osu".to_string(),
+        mode_rating,
+        pp,
+    })
+}
+
+/// Rates of an osu!standard, taiko or catch beatmap, rated with rosu-pp only
+pub fn process_other_mode(osu_map: &str, timing: ChartTiming, bpm: f64) -> Result<Vec<Rates>> {
+    let map = rosu_pp::Beatmap::from_str(osu_map)?;
+
+    Ok(CLOCK_RATES
+        .iter()
+        .filter_map(|rate| {
+            let rating = create_mode_rating(&map, *rate)?;
+            let timing = timing.at_rate(*rate);
+            Some(Rates {
+                id: None,
+                centirate: (rate * 100.0).round() as i32,
+                drain_time: timing.drain_time,
+                total_time: timing.total_length,
+                bpm: bpm * rate,
+                rating: vec![rating],
+            })
+        })
+        .collect())
 }
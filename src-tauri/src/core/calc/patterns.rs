@@ -0,0 +1,173 @@
+use super::notes::{group_rows, mania_notes, ManiaNote};
+use crate::core::react::{PatternData, PatternKind, PatternSegment, PatternShare};
+use rosu_map::Beatmap as RmBeatmap;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Rows classified together
+const WINDOW_ROWS: usize = 8;
+/// A gap longer than this (ms) between rows ends the current segment
+const BREAK_MS: f64 = 1000.0;
+/// Share of rows a feature needs to label a window
+const JACK_THRESHOLD: f64 = 0.5;
+const CHORD_THRESHOLD: f64 = 0.25;
+const SEQUENCE_THRESHOLD: f64 = 0.6;
+
+/// Label the chart with its dominant pattern over time
+pub fn classify_patterns(beatmap: &RmBeatmap) -> PatternData {
+    let notes = mania_notes(beatmap);
+    let rows = group_rows(&notes);
+
+    let mut segments: Vec<PatternSegment> = Vec::new();
+    for section in split_on_breaks(&rows) {
+        let mut previous: Option<&Vec<ManiaNote>> = None;
+        let mut continues = false;
+        for window in section.chunks(WINDOW_ROWS) {
+            let pattern = classify_window(window, previous);
+            let start_time = window[0][0].start / 1000.0;
+            let end_time = window[window.len() - 1][0].start / 1000.0;
+            let window_notes = window.iter().map(Vec::len).sum();
+
+            match segments.last_mut() {
+                Some(segment) if continues && segment.pattern == pattern => {
+                    segment.end_time = end_time;
+                    segment.notes += window_notes;
+                }
+                _ => segments.push(PatternSegment {
+                    pattern,
+                    start_time,
+                    end_time,
+                    notes: window_notes,
+                }),
+            }
+            previous = window.last();
+            continues = true;
+        }
+    }
+
+    PatternData {
+        summary: summarize(&segments, notes.len()),
+        segments,
+    }
+}
+
+/// Split rows into playable sections separated by breaks
+fn split_on_breaks(rows: &[Vec<ManiaNote>]) -> Vec<&[Vec<ManiaNote>]> {
+    let mut sections = Vec::new();
+    let mut start = 0;
+    for index in 1..rows.len() {
+        if rows[index][0].start - rows[index - 1][0].start > BREAK_MS {
+            sections.push(&rows[start..index]);
+            start = index;
+        }
+    }
+    if start < rows.len() {
+        sections.push(&rows[start..]);
+    }
+    sections
+}
+
+fn shares_column(row: &[ManiaNote], other: &[ManiaNote]) -> bool {
+    row.iter()
+        .any(|note| other.iter().any(|o| o.column == note.column))
+}
+
+fn columns(row: &[ManiaNote]) -> Vec<usize> {
+    row.iter().map(|note| note.column).collect()
+}
+
+fn classify_window(rows: &[Vec<ManiaNote>], previous: Option<&Vec<ManiaNote>>) -> PatternKind {
+    let row_count = rows.len() as f64;
+    let note_count: usize = rows.iter().map(Vec::len).sum();
+    let holds = rows
+        .iter()
+        .flatten()
+        .filter(|note| note.end.is_some())
+        .count();
+    if holds * 2 >= note_count {
+        return PatternKind::LnRelease;
+    }
+
+    let jacks = rows
+        .iter()
+        .enumerate()
+        .filter(|(index, row)| {
+            let before = if *index == 0 {
+                previous.map(Vec::as_slice)
+            } else {
+                Some(rows[index - 1].as_slice())
+            };
+            before.is_some_and(|before| shares_column(row, before))
+        })
+        .count();
+    if jacks as f64 / row_count >= JACK_THRESHOLD {
+        return if note_count as f64 / row_count >= 2.0 {
+            PatternKind::Chordjack
+        } else {
+            PatternKind::Jacks
+        };
+    }
+
+    let hands = rows.iter().filter(|row| row.len() >= 3).count() as f64;
+    let jumps: Vec<&Vec<ManiaNote>> = rows.iter().filter(|row| row.len() == 2).collect();
+    if hands / row_count >= CHORD_THRESHOLD {
+        return PatternKind::Handstream;
+    }
+    if jumps.len() as f64 / row_count >= CHORD_THRESHOLD {
+        // Brackets alternate between two disjoint jumps
+        let alternating = jumps
+            .windows(3)
+            .filter(|w| columns(w[0]) == columns(w[2]) && !shares_column(w[0], w[1]))
+            .count();
+        let is_bracket = jumps.len() as f64 / row_count >= JACK_THRESHOLD
+            && jumps.len() >= 3
+            && alternating as f64 / (jumps.len() - 2) as f64 >= SEQUENCE_THRESHOLD;
+        return if is_bracket {
+            PatternKind::Brackets
+        } else {
+            PatternKind::Jumpstream
+        };
+    }
+
+    let sequence: Vec<i64> = rows.iter().map(|row| row[0].column as i64).collect();
+    if sequence.len() >= 3 {
+        let steps = (sequence.len() - 2) as f64;
+        let trills = sequence
+            .windows(3)
+            .filter(|w| w[0] == w[2] && w[0] != w[1])
+            .count();
+        if trills as f64 / steps >= SEQUENCE_THRESHOLD {
+            return PatternKind::Trills;
+        }
+        let rolls = sequence
+            .windows(3)
+            .filter(|w| {
+                let first = (w[1] - w[0]).signum();
+                first != 0 && first == (w[2] - w[1]).signum()
+            })
+            .count();
+        if rolls as f64 / steps >= SEQUENCE_THRESHOLD {
+            return PatternKind::Rolls;
+        }
+    }
+
+    PatternKind::Stream
+}
+
+fn summarize(segments: &[PatternSegment], total_notes: usize) -> Vec<PatternShare> {
+    let mut notes: HashMap<PatternKind, usize> = HashMap::new();
+    for segment in segments {
+        *notes.entry(segment.pattern).or_insert(0) += segment.notes;
+    }
+
+    let mut summary: Vec<PatternShare> = notes
+        .into_iter()
+        .map(|(pattern, notes)| PatternShare {
+            pattern,
+            notes,
+            percentage: notes as f64 / total_notes.max(1) as f64 * 100.0,
+        })
+        .collect();
+    summary.sort_by_key(|share| Reverse(share.notes));
+    summary
+}
@@ -0,0 +1,106 @@
+use crate::core::calc::error::CalcError;
+use crate::core::calc::wife::MSD_SCORE_GOAL;
+use crate::core::react::{DifficultySection, DifficultyTimeline, ManiaRating};
+use minacalc_rs::{Calc, Note, OsuCalcExt};
+use rosu_map::Beatmap as RmBeatmap;
+use serde::Deserialize;
+
+/// Sections of the timeline, matching the default NPS graph
+const DEFAULT_SECTIONS: usize = 100;
+/// Each section is a MinaCalc run, bound them so a timeline can't compute without limit
+const MAX_SECTIONS: usize = 1_000;
+/// Shortest chart slice MinaCalc gives meaningful values for
+const DEFAULT_WINDOW_SECONDS: f64 = 8.0;
+/// Slices with fewer rows are rated 0
+const MIN_ROWS: usize = 4;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TimelineOptions {
+    pub sections: Option<usize>,
+    /// Minimum chart slice rated around each section, in seconds (of scaled time)
+    pub window_seconds: Option<f64>,
+    pub rate: Option<f32>,
+}
+
+/// Local MSD per section, each rated on a window centered on it
+pub fn calculate_difficulty_timeline(
+    calc: &Calc,
+    beatmap: &RmBeatmap,
+    options: &TimelineOptions,
+) -> Result<DifficultyTimeline, CalcError> {
+    let rate = options.rate.filter(|rate| *rate > 0.0).unwrap_or(1.0);
+    let section_count = options
+        .sections
+        .unwrap_or(DEFAULT_SECTIONS)
+        .clamp(1, MAX_SECTIONS);
+    let notes = Calc::to_notes_merged(beatmap).map_err(CalcError::calc)?;
+
+    // Note times are in unscaled seconds, the timeline is in scaled seconds
+    let first = notes
+        .first()
+        .map(|note| note.row_time as f64)
+        .unwrap_or(0.0);
+    let last = notes.last().map(|note| note.row_time as f64).unwrap_or(0.0);
+    let drain_time = (last - first) / rate as f64;
+    let section_seconds = drain_time / section_count as f64;
+    // A window never needs to be wider than the whole chart
+    let window_seconds = options
+        .window_seconds
+        .unwrap_or(DEFAULT_WINDOW_SECONDS)
+        .min(drain_time)
+        .max(section_seconds);
+
+    let mut sections = Vec::with_capacity(section_count);
+    for index in 0..section_count {
+        let start_time = index as f64 * section_seconds;
+        let end_time = start_time + section_seconds;
+        let center = (start_time + end_time) / 2.0;
+        let window_start = first + (center - window_seconds / 2.0) * rate as f64;
+        let window_end = first + (center + window_seconds / 2.0) * rate as f64;
+
+        let window: Vec<Note> = notes
+            .iter()
+            .filter(|note| {
+                (note.row_time as f64) >= window_start && (note.row_time as f64) < window_end
+            })
+            .map(|note| Note {
+                notes: note.notes,
+                row_time: note.row_time - window_start.max(first) as f32,
+            })
+            .collect();
+
+        let (overall, skillsets) = if window.len() < MIN_ROWS {
+            (0.0, ManiaRating::from_values([0.0; 7]))
+        } else {
+            let ssr = calc
+                .calc_ssr(&window, rate, MSD_SCORE_GOAL)
+                .map_err(CalcError::calc)?;
+            (
+                ssr.overall as f64,
+                ManiaRating::from_values([
+                    ssr.stream as f64,
+                    ssr.jumpstream as f64,
+                    ssr.handstream as f64,
+                    ssr.stamina as f64,
+                    ssr.jackspeed as f64,
+                    ssr.chordjack as f64,
+                    ssr.technical as f64,
+                ]),
+            )
+        };
+
+        sections.push(DifficultySection {
+            start_time,
+            end_time,
+            overall,
+            skillsets,
+        });
+    }
+
+    Ok(DifficultyTimeline {
+        centirate: (rate * 100.0).round() as i32,
+        window_seconds,
+        drain_time,
+        sections,
+    })
+}
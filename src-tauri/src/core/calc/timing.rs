@@ -0,0 +1,50 @@
+use rosu_map::section::hit_objects::HitObjectKind;
+use rosu_map::Beatmap as RmBeatmap;
+
+/// Playable length of a chart in seconds, at 1.0x
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChartTiming {
+    /// From the first object to the last object end, breaks excluded
+    pub drain_time: f64,
+    /// From the start of the song to the last object end
+    pub total_length: f64,
+}
+
+impl ChartTiming {
+    /// Timing as experienced at a playback rate
+    pub fn at_rate(&self, rate: f64) -> Self {
+        Self {
+            drain_time: self.drain_time / rate,
+            total_length: self.total_length / rate,
+        }
+    }
+}
+
+pub fn calculate_timing(beatmap: &RmBeatmap) -> ChartTiming {
+    let Some(first) = beatmap.hit_objects.first().map(|object| object.start_time) else {
+        return ChartTiming::default();
+    };
+
+    // Long notes, spinners and sliders end after they start
+    let last_end = beatmap
+        .hit_objects
+        .iter()
+        .map(|object| match &object.kind {
+            HitObjectKind::Hold(hold) => object.start_time + hold.duration,
+            HitObjectKind::Spinner(spinner) => object.start_time + spinner.duration,
+            HitObjectKind::Slider(_) => object.clone().end_time(),
+            HitObjectKind::Circle(_) => object.start_time,
+        })
+        .fold(first, f64::max);
+
+    let breaks: f64 = beatmap
+        .breaks
+        .iter()
+        .map(|period| (period.end_time.min(last_end) - period.start_time.max(first)).max(0.0))
+        .sum();
+
+    ChartTiming {
+        drain_time: ((last_end - first - breaks) / 1000.0).max(0.0),
+        total_length: last_end / 1000.0,
+    }
+}
@@ -0,0 +1,142 @@
+use crate::core::calc::error::CalcError;
+use crate::core::react::ManiaRating;
+use minacalc_rs::{Calc, OsuCalcExt};
+use rosu_map::Beatmap as RmBeatmap;
+use serde::Serialize;
+
+/// Etterna timing scales for judges 1 through 9
+const JUDGE_SCALES: [f64; 9] = [1.50, 1.33, 1.16, 1.00, 0.84, 0.66, 0.50, 0.33, 0.20];
+const MAX_POINTS: f64 = 2.0;
+const MISS_WEIGHT: f64 = -5.5;
+const HOLD_DROP_WEIGHT: f64 = -4.5;
+/// Score goal at which Etterna defines a chart's MSD, as a fraction like every MinaCalc goal
+pub(crate) const MSD_SCORE_GOAL: f32 = 0.93;
+
+/// Wife3 accuracy of a play under Etterna judges
+#[derive(Debug, Clone, Serialize)]
+pub struct WifeRescore {
+    pub notes: usize,
+    pub misses: usize,
+    pub dropped_holds: usize,
+    pub j4: f64,
+    pub j5: f64,
+}
+
+/// Etterna-style rating of a play: SSR at the achieved Wife3 J4 accuracy
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoreRating {
+    pub centirate: i32,
+    pub wife_percent: f64,
+    pub overall: f64,
+    pub skillsets: ManiaRating,
+    /// Chart MSD at this rate (SSR at 93%)
+    pub msd: f64,
+}
+
+/// Timing scale of an Etterna judge (1-9), J4 when out of range
+pub fn timing_scale(judge: u8) -> f64 {
+    JUDGE_SCALES
+        .get((judge as usize).wrapping_sub(1))
+        .copied()
+        .unwrap_or(JUDGE_SCALES[3])
+}
+
+/// Wife3 points awarded for a tap with the given offset in milliseconds
+pub fn wife3(offset_ms: f64, ts: f64) -> f64 {
+    let ridic = 5.0 * ts;
+    let max_boo_weight = 180.0 * ts;
+    let error = offset_ms.abs();
+
+    if error <= ridic {
+        return MAX_POINTS;
+    }
+
+    let zero = 65.0 * ts.powf(0.75);
+    let dev = 22.7 * ts.powf(0.75);
+    if error <= zero {
+        return MAX_POINTS * erf((zero - error) / dev);
+    }
+    if error <= max_boo_weight {
+        return (error - zero) * MISS_WEIGHT / (max_boo_weight - zero);
+    }
+    MISS_WEIGHT
+}
+
+/// Wife3 percentage from tap offsets (`None` = miss) and dropped holds
+pub fn wife3_percent(offsets: &[Option<f64>], dropped_holds: usize, judge: u8) -> f64 {
+    if offsets.is_empty() {
+        return 0.0;
+    }
+    let ts = timing_scale(judge);
+    let points: f64 = offsets
+        .iter()
+        .map(|offset| offset.map(|ms| wife3(ms, ts)).unwrap_or(MISS_WEIGHT))
+        .sum::<f64>()
+        + dropped_holds as f64 * HOLD_DROP_WEIGHT;
+    points / (offsets.len() as f64 * MAX_POINTS) * 100.0
+}
+
+/// Rescore a play on Etterna's J4 and J5
+pub fn rescore(offsets: &[Option<f64>], dropped_holds: usize) -> WifeRescore {
+    WifeRescore {
+        notes: offsets.len(),
+        misses: offsets.iter().filter(|offset| offset.is_none()).count(),
+        dropped_holds,
+        j4: wife3_percent(offsets, dropped_holds, 4),
+        j5: wife3_percent(offsets, dropped_holds, 5),
+    }
+}
+
+/// Compute the score rating of a play at `rate` with the given Wife3 J4 percentage
+pub fn score_rating(
+    calc: &Calc,
+    beatmap: &RmBeatmap,
+    rate: f32,
+    wife_percent: f64,
+) -> Result<ScoreRating, CalcError> {
+    let notes = Calc::to_notes_merged(beatmap).map_err(CalcError::calc)?;
+    // MinaCalc takes the goal as a fraction (1.0 is 100%) and rejects goals at or below 0
+    let goal = ((wife_percent / 100.0) as f32).clamp(0.0001, 1.0);
+    let ssr = calc.calc_ssr(&notes, rate, goal).map_err(CalcError::calc)?;
+    let msd = calc
+        .calc_ssr(&notes, rate, MSD_SCORE_GOAL)
+        .map_err(CalcError::calc)?;
+
+    Ok(ScoreRating {
+        centirate: (rate * 100.0).round() as i32,
+        wife_percent,
+        overall: ssr.overall as f64,
+        skillsets: ManiaRating {
+            id: None,
+            stream: ssr.stream as f64,
+            jumpstream: ssr.jumpstream as f64,
+            handstream: ssr.handstream as f64,
+            stamina: ssr.stamina as f64,
+            jackspeed: ssr.jackspeed as f64,
+            chordjack: ssr.chordjack as f64,
+            technical: ssr.technical as f64,
+        },
+        msd: msd.overall as f64,
+    })
+}
+
+/// Error function
+fn erf(x: f64) -> f64 {
+    1.0 - erfc(x)
+}
+
+/// Complementary error function (Abramowitz and Stegun 7.1.26, max error 1.5e-7)
+pub(crate) fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let tail = (((((1.061_405_429 * t - 1.453_152_027) * t) + 1.421_413_741) * t - 0.284_496_736)
+        * t
+        + 0.254_829_592)
+        * t
+        * (-z * z).exp();
+    if x < 0.0 {
+        2.0 - tail
+    } else {
+        tail
+    }
+}
@@ -0,0 +1,398 @@
+use crate::core::connection::is_osu_running;
+use crate::core::download::{download_beatmapsets, lookup_beatmapset_id, PackManifestEntry};
+use crate::core::error::{AppError, AppResult, ErrorCode};
+use crate::core::library::{osu_folder, songs_index};
+use crate::core::packmaker::{add_local_beatmap_to_pack, SharedPackMaker};
+use crate::core::preferences;
+use crate::core::stable::collection::{
+    read_collection_db, write_collection_db, Collection, CollectionBeatmap, CollectionDb,
+};
+use crate::core::stable::osdb::read_osdb;
+use futures_util::stream::{self, StreamExt};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+use tauri::AppHandle;
+
+lazy_static! {
+    static ref PENDING_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// Collection overview sent to frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionSummary {
+    pub name: String,
+    pub beatmap_count: usize,
+    pub installed_count: usize,
+    pub missing_count: usize,
+}
+
+/// Result of queueing the missing maps of a collection
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionDownload {
+    pub group_id: Option<u64>,
+    pub queued: usize,
+    /// md5s of missing maps whose beatmapset could not be looked up
+    pub unresolved: Vec<String>,
+}
+
+/// Outcome of adding a generated map to a collection
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CollectionWrite {
+    Added {
+        collection: String,
+    },
+    /// osu! is running, the map is written once it exits
+    Queued {
+        collection: String,
+    },
+    Failed {
+        collection: String,
+        message: String,
+    },
+}
+
+/// Version written when creating a new `collection.db`
+const COLLECTION_DB_VERSION: i32 = 20150203;
+/// md5 lookups sent to the mirror at once
+const LOOKUP_CONCURRENCY: usize = 4;
+
+/// Default `collection.db` location: the osu! folder containing the Songs folder
+pub fn default_collection_db_path(songs_path: &str) -> PathBuf {
+    osu_folder(songs_path).join("collection.db")
+}
+
+/// Load collections from a `collection.db` or an `.osdb` export
+pub fn load_collections(path: Option<String>) -> AppResult<Vec<Collection>> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => default_collection_db_path(&preferences::songs_path()),
+    };
+
+    let is_osdb = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("osdb"))
+        .unwrap_or(false);
+    if is_osdb {
+        read_osdb(&path)
+    } else {
+        read_collection_db(&path).map(|db| db.collections)
+    }
+}
+
+fn find_collection(collections: Vec<Collection>, name: &str) -> AppResult<Collection> {
+    collections
+        .into_iter()
+        .find(|collection| collection.name == name)
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::FileNotFound,
+                format!("Collection '{}' not found", name),
+            )
+            .with_context(name)
+        })
+}
+
+/// List collections with how many of their maps are installed
+pub fn list_collections(path: Option<String>) -> AppResult<Vec<CollectionSummary>> {
+    let collections = load_collections(path)?;
+    let index = songs_index(&preferences::songs_path())?;
+
+    Ok(collections
+        .iter()
+        .map(|collection| {
+            let installed_count = collection
+                .beatmaps
+                .iter()
+                .filter(|beatmap| index.contains_key(&beatmap.md5))
+                .count();
+            CollectionSummary {
+                name: collection.name.clone(),
+                beatmap_count: collection.beatmaps.len(),
+                installed_count,
+                missing_count: collection.beatmaps.len() - installed_count,
+            }
+        })
+        .collect())
+}
+
+/// Add every installed map of a collection to the pack, returning how many were added
+pub async fn create_pack_from_collection(
+    pack: &SharedPackMaker,
+    path: Option<String>,
+    name: String,
+) -> AppResult<usize> {
+    let collection = find_collection(load_collections(path)?, &name)?;
+    let songs_path = preferences::songs_path();
+    let index = songs_index(&songs_path)?;
+
+    let mut added = 0;
+    for beatmap in &collection.beatmaps {
+        let Some(local) = index.get(&beatmap.md5) else {
+            continue;
+        };
+        match add_local_beatmap_to_pack(pack, &songs_path, local).await {
+            Ok(()) => added += 1,
+            Err(e) => eprintln!(
+                "⚠️  Skipping {} in collection '{}': {}",
+                beatmap.md5, name, e
+            ),
+        }
+    }
+
+    {
+        let mut guard = pack.lock().await;
+        if guard.metadata.name.is_empty() {
+            guard.metadata.name = name.clone();
+        }
+    }
+
+    println!(
+        "📦 Added {} beatmaps from collection '{}' to pack",
+        added, name
+    );
+    Ok(added)
+}
+
+/// Queue downloads for the maps of a collection that are not installed
+pub async fn download_missing_from_collection(
+    app_handle: AppHandle,
+    path: Option<String>,
+    name: String,
+) -> AppResult<CollectionDownload> {
+    let collection = find_collection(load_collections(path)?, &name)?;
+    let index = songs_index(&preferences::songs_path())?;
+
+    // collection.db only stores md5s, resolve their beatmapset on the mirror
+    let lookups: Vec<(String, AppResult<i32>)> = stream::iter(
+        collection
+            .beatmaps
+            .into_iter()
+            .filter(|beatmap| !index.contains_key(&beatmap.md5)),
+    )
+    .map(|beatmap| async move {
+        let beatmapset_id = match beatmap.beatmapset_id {
+            Some(beatmapset_id) => Ok(beatmapset_id),
+            None => lookup_beatmapset_id(&beatmap.md5).await,
+        };
+        (beatmap.md5, beatmapset_id)
+    })
+    .buffer_unordered(LOOKUP_CONCURRENCY)
+    .collect()
+    .await;
+
+    let mut entries: Vec<PackManifestEntry> = Vec::new();
+    let mut unresolved = Vec::new();
+    for (md5, beatmapset_id) in lookups {
+        match beatmapset_id {
+            Ok(beatmapset_id) => {
+                if !entries
+                    .iter()
+                    .any(|entry| entry.beatmapset_id == beatmapset_id)
+                {
+                    entries.push(PackManifestEntry {
+                        beatmapset_id,
+                        title: None,
+                        creator: None,
+                    });
+                }
+            }
+            Err(e) => {
+                eprintln!("⚠️  Could not find the beatmapset of {}: {}", md5, e);
+                unresolved.push(md5);
+            }
+        }
+    }
+
+    let queued = entries.len();
+    let group_id = if entries.is_empty() {
+        None
+    } else {
+        Some(download_beatmapsets(app_handle, name, entries).await?)
+    };
+
+    Ok(CollectionDownload {
+        group_id,
+        queued,
+        unresolved,
+    })
+}
+
+/// Append beatmap md5s to a named collection of `collection.db`, creating it if needed
+///
+/// osu! keeps its collections in memory and overwrites the file on exit, so
+/// writing is refused while the game is running. The previous file is kept
+/// as `collection.db.bak`. Returns how many md5s were added.
+pub fn append_to_collection(name: &str, md5s: &[String]) -> AppResult<usize> {
+    if is_osu_running() {
+        return Err(AppError::invalid_input(
+            "osu! is running, close it before writing collection.db",
+        ));
+    }
+
+    let path = default_collection_db_path(&preferences::songs_path());
+    let mut db = if path.exists() {
+        read_collection_db(&path)?
+    } else {
+        CollectionDb {
+            version: COLLECTION_DB_VERSION,
+            collections: Vec::new(),
+        }
+    };
+
+    let index = match db.collections.iter().position(|c| c.name == name) {
+        Some(index) => index,
+        None => {
+            db.collections.push(Collection {
+                name: name.to_string(),
+                beatmaps: Vec::new(),
+            });
+            db.collections.len() - 1
+        }
+    };
+
+    let collection = &mut db.collections[index];
+    let mut added = 0;
+    for md5 in md5s {
+        if !collection
+            .beatmaps
+            .iter()
+            .any(|beatmap| &beatmap.md5 == md5)
+        {
+            collection
+                .beatmaps
+                .push(CollectionBeatmap::from_md5(md5.clone()));
+            added += 1;
+        }
+    }
+
+    if added == 0 {
+        return Ok(0);
+    }
+
+    if path.exists() {
+        let backup_path = path.with_extension("db.bak");
+        fs::copy(&path, &backup_path)
+            .map_err(|e| AppError::io("back up collection file", &backup_path, e))?;
+    }
+    write_collection_db(&path, &db)?;
+
+    println!("📝 Added {} beatmaps to collection '{}'", added, name);
+    Ok(added)
+}
+
+/// Collection entries waiting for osu! to exit, by collection name
+fn get_pending_path() -> PathBuf {
+    preferences::config_dir().join("pending_collections.json")
+}
+
+fn load_pending() -> HashMap<String, Vec<String>> {
+    fs::read_to_string(get_pending_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_pending(pending: &HashMap<String, Vec<String>>) -> AppResult<()> {
+    let path = get_pending_path();
+    if pending.is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(AppError::io("clear pending collections", &path, e))
+            }
+            _ => Ok(()),
+        };
+    }
+    let json = serde_json::to_string(pending).map_err(|e| {
+        AppError::internal(format!("Failed to serialize pending collections: {}", e))
+    })?;
+    fs::write(&path, json).map_err(|e| AppError::io("write pending collections", &path, e))
+}
+
+fn lock_pending() -> AppResult<MutexGuard<'static, ()>> {
+    PENDING_LOCK
+        .lock()
+        .map_err(|e| AppError::internal(format!("Failed to lock pending collections: {}", e)))
+}
+
+fn queue_collection_write(name: &str, md5s: &[String]) -> AppResult<()> {
+    let _guard = lock_pending()?;
+    let mut pending = load_pending();
+    let queued = pending.entry(name.to_string()).or_default();
+    for md5 in md5s {
+        if !queued.contains(md5) {
+            queued.push(md5.clone());
+        }
+    }
+    save_pending(&pending)
+}
+
+/// Add md5s to a collection now, or queue them until osu! exits if it is running
+pub fn add_to_collection(name: &str, md5s: &[String]) -> CollectionWrite {
+    let collection = name.to_string();
+    let result = if is_osu_running() {
+        queue_collection_write(name, md5s).map(|_| {
+            println!(
+                "🕒 Queued {} beatmaps for collection '{}'",
+                md5s.len(),
+                name
+            );
+            CollectionWrite::Queued {
+                collection: collection.clone(),
+            }
+        })
+    } else {
+        append_to_collection(name, md5s).map(|_| CollectionWrite::Added {
+            collection: collection.clone(),
+        })
+    };
+
+    result.unwrap_or_else(|error| {
+        eprintln!(
+            "⚠️  Could not add beatmaps to collection '{}': {}",
+            name, error
+        );
+        CollectionWrite::Failed {
+            collection,
+            message: error.to_string(),
+        }
+    })
+}
+
+/// Write the queued collection entries, to call once osu! is not running
+///
+/// Collections that fail stay queued for the next attempt. Returns how many md5s were added.
+pub fn flush_pending_collections() -> AppResult<usize> {
+    let _guard = lock_pending()?;
+    let mut pending = load_pending();
+    if pending.is_empty() {
+        return Ok(0);
+    }
+
+    let mut added = 0;
+    let mut errors = Vec::new();
+    pending.retain(|name, md5s| match append_to_collection(name, md5s) {
+        Ok(count) => {
+            added += count;
+            false
+        }
+        Err(e) => {
+            errors.push(format!("'{}': {}", name, e));
+            true
+        }
+    });
+    save_pending(&pending)?;
+
+    if errors.is_empty() {
+        Ok(added)
+    } else {
+        Err(AppError::new(
+            ErrorCode::Io,
+            format!("Failed to write collections {}", errors.join(", ")),
+        ))
+    }
+}
@@ -0,0 +1,101 @@
+use super::is_process_alive;
+use crate::core::preferences;
+use crate::core::stable::user_config::UserConfig;
+use rosu_mem::process::Process;
+use rosu_memory_lib::reader::common::stable::memory::game_state;
+use rosu_memory_lib::reader::common::{CommonReader, OsuClientKind};
+use rosu_memory_lib::reader::structs::State;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// What is known about the attached osu! client, to tell why reading fails on a setup
+#[derive(Clone, Debug, Serialize)]
+pub struct ConnectionDetails {
+    pub pid: i64,
+    /// Build from the user's osu! config, e.g. `b20250107.1`
+    pub client_version: Option<String>,
+    /// Screen osu! is on at the last heartbeat, e.g. `SongSelect`
+    pub game_state: Option<String>,
+    /// Existing Songs folder of the running client, see `detect_songs_folder`
+    pub songs_folder: Option<String>,
+    /// Unix time in milliseconds of the last successful read
+    pub last_heartbeat: Option<u64>,
+    /// Time the last heartbeat reads took
+    pub read_latency_ms: Option<f64>,
+}
+
+impl ConnectionDetails {
+    pub fn new(process: &Process, state: &mut State) -> Self {
+        let config = process.executable_dir.as_deref().and_then(UserConfig::load);
+        let client_version = config
+            .as_ref()
+            .and_then(|config| config.client_version().map(str::to_string));
+        let songs_folder = detect_songs_folder(process, state, config.as_ref())
+            .map(|path| path.display().to_string());
+        if let Some(songs_folder) = &songs_folder {
+            preferences::set_detected_songs_path(songs_folder.clone());
+        }
+
+        let mut details = Self {
+            pid: i64::from(process.pid),
+            client_version,
+            game_state: None,
+            songs_folder,
+            last_heartbeat: None,
+            read_latency_ms: None,
+        };
+        details.heartbeat(process, state);
+        details
+    }
+
+    /// Refresh the game state and timings, returns false once the process is gone
+    pub fn heartbeat(&mut self, process: &Process, state: &mut State) -> bool {
+        let started = Instant::now();
+        if !is_process_alive(process, state) {
+            return false;
+        }
+        let current = game_state(process, state);
+        self.read_latency_ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+
+        self.game_state = current.ok().map(|game_state| format!("{:?}", game_state));
+        self.last_heartbeat = Some(unix_now_ms());
+        true
+    }
+}
+
+/// Find the Songs folder of the running client
+///
+/// Tries the path osu! holds in memory, then `BeatmapDirectory` from the user's config, then the
+/// default `Songs` next to the executable, and keeps the first one that exists.
+fn detect_songs_folder(
+    process: &Process,
+    state: &mut State,
+    config: Option<&UserConfig>,
+) -> Option<PathBuf> {
+    let osu_dir = process.executable_dir.as_deref();
+    let from_memory = CommonReader::new(process, state, OsuClientKind::Stable)
+        .path_folder()
+        .ok();
+    let from_config = config
+        .and_then(UserConfig::beatmap_directory)
+        .map(PathBuf::from);
+    let default = Some(PathBuf::from("Songs"));
+
+    [from_memory, from_config, default]
+        .into_iter()
+        .flatten()
+        .map(|path| match osu_dir {
+            // Linux builds of the reader may report paths relative to the install
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path,
+        })
+        .find(|path| preferences::is_valid_songs_path(path))
+}
+
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
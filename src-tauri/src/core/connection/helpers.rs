@@ -1,26 +1,28 @@
-use super::types::ConnectionStatus;
+use super::details::ConnectionDetails;
+use super::types::{ConnectionState, ConnectionStatus};
 use tauri::{AppHandle, Emitter};
 
 /// Emit connection status to frontend
 pub(crate) fn emit_status(app_handle: &AppHandle, status: ConnectionStatus) {
-    let is_connected = status.connected;
+    let state = status.state;
 
     if let Err(e) = app_handle.emit("connection-status", status) {
         eprintln!("❌ Error emitting connection status: {}", e);
         return;
     }
 
-    if is_connected {
-        println!("✅ Connection status: Connected to osu!");
-    } else {
-        println!("❌ Connection status: Failed to connect");
+    match state {
+        ConnectionState::Searching => println!("🔍 Connection status: Searching for osu!"),
+        ConnectionState::Connected => println!("✅ Connection status: Connected to osu!"),
+        ConnectionState::Lost => println!("❌ Connection status: Lost connection to osu!"),
+        ConnectionState::Stopped => println!("🛑 Connection status: Monitoring stopped"),
     }
 }
 
-/// Check if error indicates osu! is not running
-pub fn is_osu_not_running_error(error: &str) -> bool {
-    error.contains("Process not found")
-        || error.contains("osu!.exe not found")
-        || error.contains("Access denied")
-        || error.contains("could not find osu process")
+/// Send refreshed details of a live connection, without logging every beat
+pub(crate) fn emit_heartbeat(app_handle: &AppHandle, details: &ConnectionDetails) {
+    let status = ConnectionStatus::connected(details.clone());
+    if let Err(e) = app_handle.emit("connection-status", status) {
+        eprintln!("❌ Error emitting connection heartbeat: {}", e);
+    }
 }
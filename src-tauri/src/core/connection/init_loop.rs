@@ -1,71 +1,32 @@
+use crate::core::error::{AppError, AppResult, ErrorCode};
 use rosu_mem::process::{Process, ProcessTraits};
 use rosu_memory_lib::reader::structs::State;
 use rosu_memory_lib::reader::structs::StaticAddresses;
-use rosu_memory_lib::Error;
-use std::time::Duration;
 
 static EXCLUDE_WORDS: [&str; 2] = ["umu-run", "waitforexitandrun"];
 
-#[allow(dead_code)]
-pub fn init_loop(sleep_duration: u64) -> Result<(State, Process), Error> {
-    let mut state = State {
-        addresses: StaticAddresses::default(),
-    };
-
-    let mut attempts = 0;
-    const MAX_ATTEMPTS: u32 = 3; // Arrêter après 10 tentatives
-
-    fn sleep_ms(ms: u64) {
-        std::thread::sleep(Duration::from_millis(ms));
-    }
-
-    fn read_static_addresses(p: &Process) -> Result<StaticAddresses, Error> {
-        StaticAddresses::new(p)
-    }
-
-    fn handle_error(e: Error, sleep_duration: u64) {
-        if let Error::MemoryRead(msg) = e {
-            if msg.contains("Process not found") {
-                std::thread::sleep(Duration::from_millis(sleep_duration));
-                return;
-            }
-            #[cfg(target_os = "windows")]
-            if msg.contains("OS error") {
-                std::thread::sleep(Duration::from_millis(sleep_duration));
-                return;
-            }
-        }
-        std::thread::sleep(Duration::from_millis(sleep_duration));
-    }
-
-    fn try_initialize_process() -> Option<Process> {
-        Process::initialize("osu!.exe", &EXCLUDE_WORDS).ok()
-    }
-
-    fn try_read_addresses(p: &Process, sleep_duration: u64) -> Option<StaticAddresses> {
-        match read_static_addresses(p) {
-            Ok(v) => Some(v),
-            Err(e) => {
-                handle_error(e, sleep_duration);
-                None
-            }
-        }
-    }
-
-    loop {
-        attempts += 1;
-
-        if let Some(p) = try_initialize_process() {
-            if let Some(v) = try_read_addresses(&p, sleep_duration) {
-                state.addresses = v;
-                return Ok((state, p));
-            }
-        }
+/// Check whether an osu! stable process is currently running
+pub fn is_osu_running() -> bool {
+    Process::initialize("osu!.exe", &EXCLUDE_WORDS).is_ok()
+}
 
-        sleep_ms(sleep_duration);
+/// Check whether the osu! process we attached to is still running
+///
+/// Static addresses stay readable for the whole life of the process, so a failed read means it exited.
+pub fn is_process_alive(process: &Process, state: &State) -> bool {
+    process.read_i32(state.addresses.base).is_ok()
+}
 
-        if attempts >= MAX_ATTEMPTS {
-            return Err(Error::MemoryRead("could not find osu process".to_string()));
-        }
-    }
+/// Attach to osu! and locate its static addresses, a single attempt
+pub fn try_connect() -> AppResult<(State, Process)> {
+    let process =
+        Process::initialize("osu!.exe", &EXCLUDE_WORDS).map_err(|_| AppError::osu_not_running())?;
+    let addresses = StaticAddresses::new(&process).map_err(|e| {
+        AppError::new(
+            ErrorCode::Memory,
+            format!("Could not read osu! memory addresses: {}", e),
+        )
+    })?;
+
+    Ok((State { addresses }, process))
 }
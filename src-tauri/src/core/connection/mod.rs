@@ -1,35 +1,43 @@
+mod details;
 pub(crate) mod helpers;
 pub(crate) mod monitoring;
 mod types;
 
-use helpers::emit_status;
-pub use init_loop::init_loop;
-use monitoring::start_monitoring;
+pub use details::ConnectionDetails;
+pub use init_loop::{is_osu_running, is_process_alive, try_connect};
+pub use monitoring::{ConnectionMonitor, SharedMonitor};
 use tauri::{AppHandle, Manager};
-pub use types::ConnectionStatus;
 mod init_loop;
 
 use crate::core::beatmap::monitoring::CurrentBeatmapWithRates;
+use crate::core::error::AppResult;
+use helpers::emit_status;
+use types::ConnectionStatus;
 
-pub async fn restart_osu_connection(app_handle: AppHandle) -> Result<(), String> {
-    // Emit reconnecting status
-    emit_status(&app_handle, ConnectionStatus::reconnecting());
-
-    // Get current beatmap state
-    let current_beatmap = app_handle.state::<CurrentBeatmapWithRates>();
-    let current_beatmap_clone = current_beatmap.inner().clone();
+/// Search for osu! right away, starting the monitor if it was stopped
+pub async fn restart_osu_connection(app_handle: AppHandle) -> AppResult<()> {
+    let current_beatmap = app_handle
+        .state::<CurrentBeatmapWithRates>()
+        .inner()
+        .clone();
+    let monitor = app_handle.state::<SharedMonitor>().inner().clone();
 
-    // Spawn monitoring thread
-    spawn_monitoring_thread(app_handle, current_beatmap_clone);
+    monitor
+        .lock()
+        .await
+        .restart(app_handle, current_beatmap)
+        .await;
 
     Ok(())
 }
 
-/// Spawn a new thread for osu! monitoring
-fn spawn_monitoring_thread(app_handle: AppHandle, current_beatmap: CurrentBeatmapWithRates) {
-    std::thread::spawn(move || {
-        tauri::async_runtime::block_on(async {
-            start_monitoring(app_handle, current_beatmap).await;
-        });
-    });
+/// Stop monitoring osu! until the connection is restarted
+pub async fn stop_osu_connection(app_handle: AppHandle) -> AppResult<()> {
+    let monitor = app_handle.state::<SharedMonitor>().inner().clone();
+
+    if monitor.lock().await.stop().await {
+        emit_status(&app_handle, ConnectionStatus::stopped());
+    }
+
+    Ok(())
 }
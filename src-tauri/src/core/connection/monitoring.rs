@@ -1,59 +1,202 @@
-use super::helpers::{emit_status, is_osu_not_running_error};
-use super::init_loop;
+use super::details::ConnectionDetails;
+use super::helpers::emit_status;
+use super::{is_osu_running, try_connect};
 use super::types::ConnectionStatus;
 use crate::core::beatmap::monitoring::{beatmap_monitoring_loop, CurrentBeatmapWithRates};
-use minacalc_rs::Calc;
+use crate::core::collection::flush_pending_collections;
+use crate::core::error::{AppError, ErrorCode};
+use rosu_mem::process::Process;
+use rosu_memory_lib::reader::structs::State;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tauri::AppHandle;
+use tokio::sync::oneshot::error::TryRecvError;
+use tokio::sync::{oneshot, Mutex, Notify};
+use tokio::time::{sleep, Duration};
 
-/// Initialize connection to osu! and start monitoring
-pub(crate) async fn start_monitoring(
+/// Delays between attempts to find osu!, the last one repeats until it starts
+const BACKOFF_MS: [u64; 5] = [500, 1000, 2000, 5000, 10000];
+
+pub type SharedMonitor = Arc<Mutex<ConnectionMonitor>>;
+
+/// What the supervisor shares with its owner while it runs
+#[derive(Default)]
+struct SearchSignal {
+    /// Set while osu! is being searched for
+    searching: AtomicBool,
+    /// Wakes the search up before its backoff delay ends
+    retry: Notify,
+}
+
+/// The thread running the supervisor and the channels to stop it
+struct MonitorTask {
+    stop: oneshot::Sender<()>,
+    done: oneshot::Receiver<()>,
+    search: Arc<SearchSignal>,
+}
+
+/// Owns the single osu! monitoring task of the app
+#[derive(Default)]
+pub struct ConnectionMonitor {
+    task: Option<MonitorTask>,
+}
+
+impl ConnectionMonitor {
+    /// Start monitoring osu!, does nothing if it is already running
+    pub fn start(&mut self, app_handle: AppHandle, current_beatmap: CurrentBeatmapWithRates) {
+        if self.task.is_some() {
+            return;
+        }
+        let (stop_tx, stop_rx) = oneshot::channel();
+        let (done_tx, done_rx) = oneshot::channel();
+        let search = Arc::new(SearchSignal::default());
+        let supervisor_search = search.clone();
+
+        // The process handle is not Send, so the supervisor gets its own thread
+        std::thread::spawn(move || {
+            tauri::async_runtime::block_on(async {
+                tokio::select! {
+                    _ = supervise(app_handle, current_beatmap, supervisor_search) => {}
+                    _ = stop_rx => println!("🛑 Osu! monitoring stopped"),
+                }
+            });
+            let _ = done_tx.send(());
+        });
+
+        self.task = Some(MonitorTask {
+            stop: stop_tx,
+            done: done_rx,
+            search,
+        });
+    }
+
+    /// Stop monitoring and wait until the supervisor has let go of the shared state
+    ///
+    /// Returns false when no supervisor was running.
+    pub async fn stop(&mut self) -> bool {
+        match self.task.take() {
+            Some(task) => {
+                let _ = task.stop.send(());
+                let _ = task.done.await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Search for osu! right away
+    ///
+    /// A running search skips its backoff delay, a connected monitor is replaced by a fresh one
+    /// and a stopped one is started.
+    pub async fn restart(
+        &mut self,
+        app_handle: AppHandle,
+        current_beatmap: CurrentBeatmapWithRates,
+    ) {
+        if let Some(task) = &mut self.task {
+            // A supervisor that already finished would never be woken up
+            let alive = matches!(task.done.try_recv(), Err(TryRecvError::Empty));
+            if !alive {
+                eprintln!("⚠️  Osu! monitoring had stopped unexpectedly, starting it again");
+                self.task = None;
+            } else if task.search.searching.load(Ordering::SeqCst) {
+                task.search.retry.notify_one();
+                return;
+            }
+        }
+        self.stop().await;
+        self.start(app_handle, current_beatmap);
+    }
+}
+
+/// Keep osu! connected: search for it, monitor it, and search again when it exits
+async fn supervise(
     app_handle: AppHandle,
     current_beatmap: CurrentBeatmapWithRates,
+    search: Arc<SearchSignal>,
 ) {
     println!("🔍 Starting memory reading...");
 
-    // Try to initialize connection
-    let (mut state, process) = match init_loop(500) {
-        Ok(result) => {
-            println!("✅ Connected to osu! - Starting beatmap monitoring...");
-            emit_status(&app_handle, ConnectionStatus::connected());
-            result
-        }
-        Err(e) => {
-            handle_init_error(&app_handle, e);
-            return;
+    let mut last_error: Option<String> = None;
+    loop {
+        // osu! rewrites collection.db on exit, queued entries can only be written while it is closed
+        if !is_osu_running() {
+            write_pending_collections();
         }
-    };
-
-    // Start monitoring loop
-    let calc = Calc::default();
-    if let Err(e) = beatmap_monitoring_loop(
-        app_handle.clone(),
-        current_beatmap,
-        &mut state,
-        &process,
-        &calc,
-    )
-    .await
-    {
-        eprintln!("❌ Beatmap monitoring error: {}", e);
-        emit_status(&app_handle, ConnectionStatus::disconnected(e.to_string()));
+        search.searching.store(true, Ordering::SeqCst);
+        let (mut state, process) = wait_for_osu(&app_handle, &search, last_error.take()).await;
+        search.searching.store(false, Ordering::SeqCst);
+        println!("✅ Connected to osu! - Starting beatmap monitoring...");
+        let mut details = ConnectionDetails::new(&process, &mut state);
+        println!(
+            "🔎 osu! pid={} version={} songs={}",
+            details.pid,
+            details.client_version.as_deref().unwrap_or("unknown"),
+            details.songs_folder.as_deref().unwrap_or("unknown")
+        );
+        emit_status(&app_handle, ConnectionStatus::connected(details.clone()));
+
+        let reason = match beatmap_monitoring_loop(
+            app_handle.clone(),
+            current_beatmap.clone(),
+            &mut state,
+            &process,
+            &mut details,
+        )
+        .await
+        {
+            Ok(()) => "osu! was closed".to_string(),
+            Err(e) => {
+                eprintln!("❌ Beatmap monitoring error: {}", e);
+                e.to_string()
+            }
+        };
+        emit_status(&app_handle, ConnectionStatus::lost(reason.clone()));
+        last_error = Some(reason);
     }
 }
 
-/// Handle initialization errors
-fn handle_init_error(app_handle: &AppHandle, error: rosu_memory_lib::Error) {
-    let error_msg = error.to_string();
-    eprintln!("❌ Initialization error: {}", error_msg);
+/// Try to attach to osu! with backoff until it succeeds
+async fn wait_for_osu(
+    app_handle: &AppHandle,
+    search: &SearchSignal,
+    last_error: Option<String>,
+) -> (State, Process) {
+    emit_status(app_handle, ConnectionStatus::searching(last_error));
 
-    emit_status(
-        app_handle,
-        ConnectionStatus::disconnected(error_msg.clone()),
-    );
+    let mut attempt = 0;
+    loop {
+        match try_connect() {
+            Ok(connection) => return connection,
+            // Only log the first failure of a search, retries fail the same way
+            Err(e) if attempt == 0 => log_connect_error(&e),
+            Err(_) => {}
+        }
+
+        let delay = BACKOFF_MS[attempt.min(BACKOFF_MS.len() - 1)];
+        attempt += 1;
+        tokio::select! {
+            _ = sleep(Duration::from_millis(delay)) => {}
+            _ = search.retry.notified() => {
+                println!("🔁 Retrying osu! connection now");
+                attempt = 0;
+            }
+        }
+    }
+}
+
+fn write_pending_collections() {
+    match flush_pending_collections() {
+        Ok(0) => {}
+        Ok(added) => println!("📝 Wrote {} queued collection entries", added),
+        Err(e) => eprintln!("⚠️  {}", e),
+    }
+}
 
-    if is_osu_not_running_error(&error_msg) {
-        println!("⚠️ Osu! not running - monitoring stopped");
+fn log_connect_error(error: &AppError) {
+    if error.code == ErrorCode::OsuNotRunning {
+        println!("⚠️ Osu! not running - waiting for it to start");
     } else {
-        println!("⚠️ Unknown initialization error - monitoring stopped");
+        eprintln!("❌ Initialization error: {}", error);
     }
 }
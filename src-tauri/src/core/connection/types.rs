@@ -1,31 +1,64 @@
+use super::details::ConnectionDetails;
 use serde::Serialize;
 
+/// Lifecycle of the link to osu!
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionState {
+    /// Waiting for an osu! process to attach to
+    Searching,
+    Connected,
+    /// osu! exited or stopped answering, a new search follows
+    Lost,
+    /// Monitoring was stopped on request, nothing is searched until it is restarted
+    Stopped,
+}
+
 /// Connection status sent to frontend
 #[derive(Clone, Serialize)]
 pub struct ConnectionStatus {
+    pub state: ConnectionState,
     pub connected: bool,
+    /// Why the last connection was lost, kept while searching again
     pub error: Option<String>,
+    /// Process and client details, only while connected
+    pub details: Option<ConnectionDetails>,
 }
 
 impl ConnectionStatus {
-    pub fn connected() -> Self {
+    pub fn searching(last_error: Option<String>) -> Self {
+        Self {
+            state: ConnectionState::Searching,
+            connected: false,
+            error: last_error,
+            details: None,
+        }
+    }
+
+    pub fn connected(details: ConnectionDetails) -> Self {
         Self {
+            state: ConnectionState::Connected,
             connected: true,
             error: None,
+            details: Some(details),
         }
     }
 
-    pub fn disconnected(error: String) -> Self {
+    pub fn lost(error: String) -> Self {
         Self {
+            state: ConnectionState::Lost,
             connected: false,
             error: Some(error),
+            details: None,
         }
     }
 
-    pub fn reconnecting() -> Self {
+    pub fn stopped() -> Self {
         Self {
+            state: ConnectionState::Stopped,
             connected: false,
-            error: Some("Reconnecting...".to_string()),
+            error: None,
+            details: None,
         }
     }
 }
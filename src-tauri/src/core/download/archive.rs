@@ -0,0 +1,47 @@
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+/// Resolve the beatmapset ID from a downloaded filename
+///
+/// Mirrors name archives `<id>.osz` or `<id> Artist - Title.osz`, so the
+/// leading digits are used when present.
+pub fn beatmapset_id_from_filename(filename: &str) -> Option<i32> {
+    let digits: String = filename
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse::<i32>().ok().filter(|id| *id > 0)
+}
+
+/// Resolve the beatmapset ID from the `.osu` files inside an `.osz` archive
+pub fn beatmapset_id_from_archive(data: &[u8]) -> Option<i32> {
+    let mut archive = ZipArchive::new(Cursor::new(data)).ok()?;
+
+    for index in 0..archive.len() {
+        let mut entry = match archive.by_index(index) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+        if !entry.name().to_lowercase().ends_with(".osu") {
+            continue;
+        }
+
+        let mut content = String::new();
+        if entry.read_to_string(&mut content).is_err() {
+            continue;
+        }
+        if let Some(id) = beatmapset_id_from_osu(&content) {
+            return Some(id);
+        }
+    }
+
+    None
+}
+
+fn beatmapset_id_from_osu(content: &str) -> Option<i32> {
+    content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("BeatmapSetID:"))
+        .and_then(|value| value.trim().parse::<i32>().ok())
+        .filter(|id| *id > 0)
+}
@@ -0,0 +1,110 @@
+use super::types::{
+    DownloadGroup, DownloadGroupStatus, DownloadState, DownloadStatus, DOWNLOAD_GROUPS,
+    DOWNLOAD_MANAGER,
+};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+
+/// Register a new download group
+pub fn register_group(group_id: u64, name: String, job_ids: Vec<u64>) {
+    if let Ok(mut groups) = DOWNLOAD_GROUPS.lock() {
+        groups.insert(
+            group_id,
+            DownloadGroup {
+                group_id,
+                name,
+                job_ids,
+            },
+        );
+    }
+}
+
+/// Compute the aggregate status of a group from its member downloads
+fn group_status(
+    group: &DownloadGroup,
+    manager: &HashMap<u64, DownloadStatus>,
+) -> DownloadGroupStatus {
+    let mut completed = 0;
+    let mut failed = 0;
+    let mut progress_sum = 0.0;
+
+    for status in group.job_ids.iter().filter_map(|id| manager.get(id)) {
+        match status.status {
+            DownloadState::Completed => {
+                completed += 1;
+                progress_sum += 100.0;
+            }
+            DownloadState::Failed => {
+                failed += 1;
+                progress_sum += 100.0;
+            }
+            _ => progress_sum += status.progress,
+        }
+    }
+
+    let total = group.job_ids.len();
+    let progress = if total > 0 {
+        progress_sum / total as f32
+    } else {
+        100.0
+    };
+
+    DownloadGroupStatus {
+        group_id: group.group_id,
+        name: group.name.clone(),
+        total,
+        completed,
+        failed,
+        progress,
+        finished: completed + failed >= total,
+    }
+}
+
+/// Emit aggregate progress for every group still in flight, finished ones are already removed
+pub fn emit_group_status(app_handle: &AppHandle) {
+    let status_list: Vec<DownloadGroupStatus> =
+        match (DOWNLOAD_GROUPS.lock(), DOWNLOAD_MANAGER.lock()) {
+            (Ok(groups), Ok(manager)) => groups
+                .values()
+                .map(|group| group_status(group, &manager))
+                .collect(),
+            _ => return,
+        };
+
+    if !status_list.is_empty() {
+        let _ = app_handle.emit("download-group-update", status_list);
+    }
+}
+
+/// Emit `download-group-completed` and forget the group once every member is done or failed
+pub fn check_group_completion(app_handle: &AppHandle, job_id: u64) {
+    let group_id = match DOWNLOAD_MANAGER.lock() {
+        Ok(manager) => manager.get(&job_id).and_then(|status| status.group_id),
+        Err(_) => return,
+    };
+    let Some(group_id) = group_id else {
+        return;
+    };
+
+    let finished_status = match (DOWNLOAD_GROUPS.lock(), DOWNLOAD_MANAGER.lock()) {
+        (Ok(mut groups), Ok(manager)) => {
+            let status = groups
+                .get(&group_id)
+                .map(|group| group_status(group, &manager))
+                .filter(|status| status.finished);
+            if status.is_some() {
+                groups.remove(&group_id);
+            }
+            status
+        }
+        _ => None,
+    };
+
+    if let Some(status) = finished_status {
+        println!(
+            "✅ Download group '{}' finished: {} completed, {} failed",
+            status.name, status.completed, status.failed
+        );
+        let _ = app_handle.emit("download-group-completed", status);
+    }
+}
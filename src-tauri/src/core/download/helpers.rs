@@ -1,13 +1,14 @@
+use super::group::{check_group_completion, emit_group_status};
 use super::types::{DownloadState, DownloadStatus, DOWNLOAD_MANAGER};
 use tauri::{AppHandle, Emitter};
 
 /// Update download status in the manager
-pub fn update_status<F>(beatmapset_id: i32, updater: F)
+pub fn update_status<F>(job_id: u64, updater: F)
 where
     F: FnOnce(&mut DownloadStatus),
 {
     if let Ok(mut manager) = DOWNLOAD_MANAGER.lock() {
-        if let Some(status) = manager.get_mut(&beatmapset_id) {
+        if let Some(status) = manager.get_mut(&job_id) {
             updater(status);
         }
     }
@@ -21,24 +22,27 @@ pub fn emit_status(app_handle: &AppHandle) {
         .unwrap_or_default();
 
     let _ = app_handle.emit("download-status-update", status_list);
+    emit_group_status(app_handle);
 }
 
 /// Mark download as failed
-pub fn mark_failed(app_handle: &AppHandle, beatmapset_id: i32, error: String) {
-    update_status(beatmapset_id, |status| {
+pub fn mark_failed(app_handle: &AppHandle, job_id: u64, error: String) {
+    update_status(job_id, |status| {
         status.status = DownloadState::Failed;
         status.error = Some(error.clone());
     });
     emit_status(app_handle);
+    check_group_completion(app_handle, job_id);
 }
 
 /// Mark download as completed
-pub fn mark_completed(app_handle: &AppHandle, beatmapset_id: i32) {
-    update_status(beatmapset_id, |status| {
+pub fn mark_completed(app_handle: &AppHandle, job_id: u64) {
+    update_status(job_id, |status| {
         status.status = DownloadState::Completed;
         status.progress = 100.0;
     });
     emit_status(app_handle);
+    check_group_completion(app_handle, job_id);
 }
 
 /// Calculate download progress percentage
@@ -1,3 +1,4 @@
+use crate::core::error::{AppError, AppResult, ErrorCode};
 use reqwest::{Client, Response};
 
 const USER_AGENT: &str = "osef-me-downloader/1.0";
@@ -8,7 +9,7 @@ pub fn create_client() -> Client {
 }
 
 /// Download file from URL
-pub async fn fetch_beatmap(url: &str) -> Result<Response, String> {
+pub async fn fetch_beatmap(url: &str) -> AppResult<Response> {
     let client = create_client();
 
     let response = client
@@ -16,12 +17,69 @@ pub async fn fetch_beatmap(url: &str) -> Result<Response, String> {
         .header("User-Agent", USER_AGENT)
         .header("Accept", "*/*")
         .send()
-        .await
-        .map_err(|e| format!("HTTP request failed: {}", e))?;
+        .await?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP {}", response.status()));
+        return Err(
+            AppError::new(ErrorCode::Network, format!("HTTP {}", response.status()))
+                .with_context(url),
+        );
     }
 
     Ok(response)
 }
+
+/// Extract the filename advertised by the `Content-Disposition` header
+///
+/// Prefers the RFC 5987 `filename*` parameter over the plain `filename` one.
+pub fn filename_from_headers(response: &Response) -> Option<String> {
+    let header = response
+        .headers()
+        .get(reqwest::header::CONTENT_DISPOSITION)?
+        .to_str()
+        .ok()?;
+    parse_content_disposition(header)
+}
+
+fn parse_content_disposition(header: &str) -> Option<String> {
+    let mut plain = None;
+    let mut extended = None;
+
+    for param in header.split(';').map(str::trim) {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                // Format: charset'language'percent-encoded-value
+                let encoded = value.trim().splitn(3, '\'').nth(2).unwrap_or(value);
+                extended = Some(percent_decode(encoded));
+            }
+            "filename" => {
+                plain = Some(value.trim().trim_matches('"').to_string());
+            }
+            _ => {}
+        }
+    }
+
+    extended.or(plain).filter(|name| !name.is_empty())
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
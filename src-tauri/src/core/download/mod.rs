@@ -1,27 +1,41 @@
+mod archive;
+mod group;
 mod helpers;
 mod http;
 mod stream;
 mod types;
 
+use crate::core::error::{AppError, AppResult};
 use crate::core::preferences;
+use archive::{beatmapset_id_from_archive, beatmapset_id_from_filename};
+use group::register_group;
 use helpers::{emit_status, mark_completed, mark_failed, update_status};
-use http::fetch_beatmap;
+use http::{fetch_beatmap, filename_from_headers};
 use std::fs;
 use std::path::PathBuf;
 use stream::stream_download;
 use tauri::AppHandle;
-use types::{DownloadState, DownloadStatus, DOWNLOAD_MANAGER, DOWNLOAD_SEMAPHORE};
+use types::{
+    next_group_id, next_job_id, DownloadState, DownloadStatus, MirrorBeatmap,
+    DOWNLOAD_MANAGER, DOWNLOAD_SEMAPHORE,
+};
+pub use types::{PackManifest, PackManifestEntry};
+
+/// Mirror used when only a beatmapset ID is known
+const MIRROR_DOWNLOAD_URL: &str = "https://catboy.best/d/";
+/// Mirror API returning the beatmap with a given md5
+const MIRROR_MD5_URL: &str = "https://catboy.best/api/v2/md5/";
 
 /// Test download event for debugging (core)
-pub async fn test_download_event(
-    app_handle: AppHandle,
-    beatmapset_id: i32,
-) -> Result<String, String> {
+pub async fn test_download_event(app_handle: AppHandle, beatmapset_id: i32) -> AppResult<String> {
+    let job_id = next_job_id();
     if let Ok(mut manager) = DOWNLOAD_MANAGER.lock() {
         manager.insert(
-            beatmapset_id,
+            job_id,
             DownloadStatus {
-                beatmapset_id,
+                job_id,
+                group_id: None,
+                beatmapset_id: Some(beatmapset_id),
                 filename: format!("test_{}.osz", beatmapset_id),
                 display_name: "Test Beatmap by Test Creator".to_string(),
                 status: DownloadState::Downloading,
@@ -38,46 +52,145 @@ pub async fn test_download_event(
 }
 
 /// Queue and start a download from URL (core)
+///
+/// The download is tracked under an internally generated job ID. When
+/// `beatmapset_id` is not provided it is resolved from the response headers
+/// or, failing that, from the downloaded archive.
 pub async fn download_beatmap_from_url(
     app_handle: AppHandle,
     url: String,
-    filename: String,
+    beatmapset_id: Option<i32>,
+    filename: Option<String>,
     beatmapset_name: String,
     creator: String,
-) -> Result<String, String> {
-    let beatmapset_id = extract_beatmapset_id(&filename);
+) -> AppResult<String> {
     let display_name = format!("{} by {}", beatmapset_name, creator);
 
+    spawn_download(
+        app_handle,
+        next_job_id(),
+        url,
+        beatmapset_id,
+        filename,
+        display_name,
+        None,
+    );
+
+    Ok(format!("Download started for {}", beatmapset_name))
+}
+
+/// Queue a list of beatmapsets as a single download group (core)
+///
+/// Returns the group ID; a `download-group-completed` event is emitted once
+/// every member download has completed or failed.
+pub async fn download_beatmapsets(
+    app_handle: AppHandle,
+    name: String,
+    entries: Vec<PackManifestEntry>,
+) -> AppResult<u64> {
+    if entries.is_empty() {
+        return Err(AppError::invalid_input("No beatmapsets to download"));
+    }
+
+    let group_id = next_group_id();
+    let mut seen = std::collections::HashSet::new();
+    let entries: Vec<PackManifestEntry> = entries
+        .into_iter()
+        .filter(|entry| seen.insert(entry.beatmapset_id))
+        .collect();
+
+    // Register the group before starting members so early completions are counted
+    let job_ids: Vec<u64> = entries.iter().map(|_| next_job_id()).collect();
+    register_group(group_id, name.clone(), job_ids.clone());
+
+    for (job_id, entry) in job_ids.into_iter().zip(entries) {
+        let display_name = match (&entry.title, &entry.creator) {
+            (Some(title), Some(creator)) => format!("{} by {}", title, creator),
+            (Some(title), None) => title.clone(),
+            _ => format!("Beatmapset {}", entry.beatmapset_id),
+        };
+        let url = format!("{}{}", MIRROR_DOWNLOAD_URL, entry.beatmapset_id);
+        spawn_download(
+            app_handle.clone(),
+            job_id,
+            url,
+            Some(entry.beatmapset_id),
+            None,
+            display_name,
+            Some(group_id),
+        );
+    }
+
+    println!("📦 Download group '{}' queued ({} beatmapsets)", name, seen.len());
+    Ok(group_id)
+}
+
+/// Look up the beatmapset of a beatmap from its md5 on the mirror
+pub async fn lookup_beatmapset_id(md5: &str) -> AppResult<i32> {
+    let url = format!("{}{}", MIRROR_MD5_URL, md5);
+    let beatmap: MirrorBeatmap = fetch_beatmap(&url).await?.json().await?;
+    Ok(beatmap.beatmapset_id)
+}
+
+/// Queue every beatmapset listed in an exported pack manifest (core)
+pub async fn download_pack_manifest(
+    app_handle: AppHandle,
+    manifest_path: String,
+) -> AppResult<u64> {
+    let content = fs::read_to_string(&manifest_path)
+        .map_err(|e| AppError::io("read pack manifest", &manifest_path, e))?;
+    let manifest: PackManifest = serde_json::from_str(&content)
+        .map_err(|e| AppError::parse("pack manifest", e).with_context(&manifest_path))?;
+
+    download_beatmapsets(app_handle, manifest.name, manifest.beatmapsets).await
+}
+
+/// Queue a download under the given job ID and start it in background
+fn spawn_download(
+    app_handle: AppHandle,
+    job_id: u64,
+    url: String,
+    beatmapset_id: Option<i32>,
+    filename: Option<String>,
+    display_name: String,
+    group_id: Option<u64>,
+) {
+    let filename = filename.filter(|name| !name.trim().is_empty());
+
     // Queue download
-    queue_download(beatmapset_id, filename.clone(), display_name);
+    queue_download(
+        job_id,
+        group_id,
+        beatmapset_id,
+        filename.clone().unwrap_or_default(),
+        display_name,
+    );
     emit_status(&app_handle);
 
     // Start download in background
     tauri::async_runtime::spawn(async move {
-        let result = download_beatmap(app_handle.clone(), beatmapset_id, url, filename).await;
+        let result = download_beatmap(app_handle.clone(), job_id, url, filename).await;
 
         if let Err(e) = result {
-            eprintln!("❌ Download failed for beatmap {}: {}", beatmapset_id, e);
+            eprintln!("❌ Download job {} failed: {}", job_id, e);
         }
     });
-
-    Ok(format!("Download started for {}", beatmapset_name))
-}
-
-/// Extract beatmapset ID from filename
-fn extract_beatmapset_id(filename: &str) -> i32 {
-    filename
-        .strip_suffix(".osz")
-        .and_then(|s| s.parse::<i32>().ok())
-        .unwrap_or(0)
 }
 
 /// Add download to queue
-fn queue_download(beatmapset_id: i32, filename: String, display_name: String) {
+fn queue_download(
+    job_id: u64,
+    group_id: Option<u64>,
+    beatmapset_id: Option<i32>,
+    filename: String,
+    display_name: String,
+) {
     if let Ok(mut manager) = DOWNLOAD_MANAGER.lock() {
         manager.insert(
-            beatmapset_id,
+            job_id,
             DownloadStatus {
+                job_id,
+                group_id,
                 beatmapset_id,
                 filename,
                 display_name,
@@ -91,71 +204,125 @@ fn queue_download(beatmapset_id: i32, filename: String, display_name: String) {
     }
 }
 
+/// Read the beatmapset ID currently known for a job
+fn known_beatmapset_id(job_id: u64) -> Option<i32> {
+    DOWNLOAD_MANAGER
+        .lock()
+        .ok()
+        .and_then(|manager| manager.get(&job_id).and_then(|status| status.beatmapset_id))
+}
+
 /// Execute download process
 async fn download_beatmap(
     app_handle: AppHandle,
-    beatmapset_id: i32,
+    job_id: u64,
     url: String,
-    filename: String,
-) -> Result<(), String> {
+    filename: Option<String>,
+) -> AppResult<()> {
     // Load config and prepare paths
-    let songs_path = get_songs_path()?;
-    ensure_directory_exists(&songs_path)?;
+    let songs_path = get_songs_path().and_then(|path| {
+        ensure_directory_exists(&path)?;
+        Ok(path)
+    });
+    let songs_path = songs_path.inspect_err(|e| mark_failed(&app_handle, job_id, e.to_string()))?;
 
-    let file_path = songs_path.join(&filename);
+    // Acquire download slot
+    let _permit = DOWNLOAD_SEMAPHORE.acquire().await;
 
     // Update status to downloading
-    update_status(beatmapset_id, |status| {
+    update_status(job_id, |status| {
         status.status = DownloadState::Downloading;
     });
     emit_status(&app_handle);
 
-    // Acquire download slot
-    let _permit = DOWNLOAD_SEMAPHORE.acquire().await;
-
     // Fetch and stream download
-    let response = fetch_beatmap(&url).await.map_err(|e| {
-        mark_failed(&app_handle, beatmapset_id, e.clone());
-        e
-    })?;
+    let response = fetch_beatmap(&url)
+        .await
+        .inspect_err(|e| mark_failed(&app_handle, job_id, e.to_string()))?;
 
-    let _ = response.content_length();
+    // Resolve beatmapset ID and filename from headers when not provided
+    let header_filename = filename_from_headers(&response);
+    let mut beatmapset_id = known_beatmapset_id(job_id)
+        .or_else(|| header_filename.as_deref().and_then(beatmapset_id_from_filename));
 
-    let buffer = stream_download(response, beatmapset_id, &app_handle)
+    let buffer = stream_download(response, job_id, &app_handle)
         .await
-        .map_err(|e| {
-            mark_failed(&app_handle, beatmapset_id, e.clone());
-            e
-        })?;
+        .inspect_err(|e| mark_failed(&app_handle, job_id, e.to_string()))?;
+
+    // Fall back to the archive contents
+    if beatmapset_id.is_none() {
+        beatmapset_id = beatmapset_id_from_archive(&buffer);
+    }
+
+    let filename = resolve_filename(filename, header_filename, beatmapset_id, job_id);
+    update_status(job_id, |status| {
+        status.beatmapset_id = beatmapset_id;
+        status.filename = filename.clone();
+    });
 
     // Save file
-    save_file(&file_path, &buffer).map_err(|e| {
-        mark_failed(&app_handle, beatmapset_id, e.clone());
-        e
-    })?;
+    let file_path = songs_path.join(&filename);
+    save_file(&file_path, &buffer)
+        .inspect_err(|e| mark_failed(&app_handle, job_id, e.to_string()))?;
 
-    mark_completed(&app_handle, beatmapset_id);
+    mark_completed(&app_handle, job_id);
 
     Ok(())
 }
 
+/// Pick the on-disk filename: explicit, then `Content-Disposition`, then ID based
+fn resolve_filename(
+    explicit: Option<String>,
+    from_headers: Option<String>,
+    beatmapset_id: Option<i32>,
+    job_id: u64,
+) -> String {
+    let name = explicit.or(from_headers).unwrap_or_else(|| match beatmapset_id {
+        Some(id) => format!("{}.osz", id),
+        None => format!("download_{}.osz", job_id),
+    });
+
+    let mut name = sanitize_filename(&name);
+    if name.is_empty() {
+        name = format!("download_{}", job_id);
+    }
+    if !name.to_lowercase().ends_with(".osz") {
+        name.push_str(".osz");
+    }
+    name
+}
+
+/// Strip path separators and characters Windows refuses in filenames
+fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '<' | '>' | '"' | '|' | '?' | '*' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .trim_start_matches('.')
+        .to_string()
+}
+
 /// Get songs path from config
-fn get_songs_path() -> Result<PathBuf, String> {
-    let config = std::panic::catch_unwind(preferences::load_config)
-        .map_err(|_| "Failed to load config".to_string())?;
+fn get_songs_path() -> AppResult<PathBuf> {
+    let songs_path = std::panic::catch_unwind(preferences::songs_path)
+        .map_err(|_| AppError::internal("Failed to load config"))?;
 
-    Ok(PathBuf::from(&config.songs_path))
+    Ok(PathBuf::from(songs_path))
 }
 
 /// Ensure directory exists
-fn ensure_directory_exists(path: &PathBuf) -> Result<(), String> {
+fn ensure_directory_exists(path: &PathBuf) -> AppResult<()> {
     if !path.exists() {
-        fs::create_dir_all(path).map_err(|e| format!("Failed to create directory: {}", e))?;
+        fs::create_dir_all(path).map_err(|e| AppError::io("create directory", path, e))?;
     }
     Ok(())
 }
 
 /// Save buffer to file
-fn save_file(path: &PathBuf, data: &[u8]) -> Result<(), String> {
-    fs::write(path, data).map_err(|e| format!("Failed to save file: {}", e))
+fn save_file(path: &PathBuf, data: &[u8]) -> AppResult<()> {
+    fs::write(path, data).map_err(|e| AppError::io("save file", path, e))
 }
@@ -1,4 +1,5 @@
 use super::helpers::{calculate_progress, emit_status, update_status};
+use crate::core::error::{AppError, AppResult, ErrorCode};
 use futures_util::StreamExt;
 use reqwest::Response;
 use tauri::AppHandle;
@@ -8,13 +9,13 @@ const PROGRESS_UPDATE_INTERVAL: u64 = 100 * 1024; // 100KB
 /// Stream download with progress tracking
 pub async fn stream_download(
     response: Response,
-    beatmapset_id: i32,
+    job_id: u64,
     app_handle: &AppHandle,
-) -> Result<Vec<u8>, String> {
+) -> AppResult<Vec<u8>> {
     let total_size = response.content_length().unwrap_or(0);
 
     // Update total size
-    update_status(beatmapset_id, |status| {
+    update_status(job_id, |status| {
         status.total_bytes = Some(total_size);
     });
     emit_status(app_handle);
@@ -24,14 +25,15 @@ pub async fn stream_download(
     let mut buffer = Vec::with_capacity(total_size as usize);
 
     while let Some(chunk_result) = stream.next().await {
-        let chunk = chunk_result.map_err(|e| format!("Stream error: {}", e))?;
+        let chunk = chunk_result
+            .map_err(|e| AppError::new(ErrorCode::Network, format!("Stream error: {}", e)))?;
 
         downloaded += chunk.len() as u64;
         buffer.extend_from_slice(&chunk);
 
         let progress = calculate_progress(downloaded, total_size);
 
-        update_status(beatmapset_id, |status| {
+        update_status(job_id, |status| {
             status.downloaded_bytes = downloaded;
             status.progress = progress;
         });
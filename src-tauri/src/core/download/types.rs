@@ -1,14 +1,18 @@
 use lazy_static::lazy_static;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::sync::Semaphore;
 
-pub type DownloadManager = Arc<Mutex<HashMap<i32, DownloadStatus>>>;
+pub type DownloadManager = Arc<Mutex<HashMap<u64, DownloadStatus>>>;
+pub type DownloadGroups = Arc<Mutex<HashMap<u64, DownloadGroup>>>;
 
 #[derive(Clone, Serialize)]
 pub struct DownloadStatus {
-    pub beatmapset_id: i32,
+    pub job_id: u64,
+    pub group_id: Option<u64>,
+    pub beatmapset_id: Option<i32>,
     pub filename: String,
     pub display_name: String,
     pub status: DownloadState,
@@ -37,7 +41,63 @@ impl std::fmt::Display for DownloadState {
     }
 }
 
+/// A set of downloads started together and tracked as one unit
+#[derive(Clone)]
+pub struct DownloadGroup {
+    pub group_id: u64,
+    pub name: String,
+    pub job_ids: Vec<u64>,
+}
+
+/// Aggregate progress of a download group sent to frontend
+#[derive(Clone, Serialize)]
+pub struct DownloadGroupStatus {
+    pub group_id: u64,
+    pub name: String,
+    pub total: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub progress: f32,
+    pub finished: bool,
+}
+
+/// Exported pack manifest listing the beatmapsets of a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    pub beatmapsets: Vec<PackManifestEntry>,
+}
+
+/// Beatmap returned by the mirror's md5 lookup, only the fields we use
+#[derive(Deserialize)]
+pub struct MirrorBeatmap {
+    pub beatmapset_id: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifestEntry {
+    pub beatmapset_id: i32,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub creator: Option<String>,
+}
+
 lazy_static! {
     pub static ref DOWNLOAD_MANAGER: DownloadManager = Arc::new(Mutex::new(HashMap::new()));
+    pub static ref DOWNLOAD_GROUPS: DownloadGroups = Arc::new(Mutex::new(HashMap::new()));
     pub static ref DOWNLOAD_SEMAPHORE: Semaphore = Semaphore::new(5);
 }
+
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_GROUP_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Allocate a unique identifier for a new download job
+pub fn next_job_id() -> u64 {
+    NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Allocate a unique identifier for a new download group
+pub fn next_group_id() -> u64 {
+    NEXT_GROUP_ID.fetch_add(1, Ordering::Relaxed)
+}
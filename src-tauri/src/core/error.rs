@@ -0,0 +1,136 @@
+use crate::core::calc::error::CalcError;
+use serde::Serialize;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+pub type AppResult<T> = Result<T, AppError>;
+
+/// Stable error codes the frontend can branch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// No beatmap is selected in osu! yet
+    NoBeatmap,
+    FileNotFound,
+    Io,
+    Parse,
+    Calc,
+    OsuNotRunning,
+    /// osu! is running but its memory could not be read
+    Memory,
+    Network,
+    InvalidInput,
+    Internal,
+}
+
+/// Error returned by every command: a code, a readable message and optional context
+#[derive(Debug, Clone, Serialize)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// What the error is about, e.g. the path of the file that failed to load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            context: None,
+        }
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    pub fn no_beatmap() -> Self {
+        Self::new(ErrorCode::NoBeatmap, "No current beatmap loaded")
+    }
+
+    pub fn osu_not_running() -> Self {
+        Self::new(ErrorCode::OsuNotRunning, "osu! is not running")
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn internal(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+
+    /// A failed file operation, reported as `FileNotFound` when the file is missing
+    pub fn io(action: &str, path: impl AsRef<Path>, error: io::Error) -> Self {
+        let code = if error.kind() == io::ErrorKind::NotFound {
+            ErrorCode::FileNotFound
+        } else {
+            ErrorCode::Io
+        };
+        Self::new(code, format!("Failed to {}: {}", action, error))
+            .with_context(path.as_ref().display().to_string())
+    }
+
+    /// A .osu file that is missing or could not be decoded
+    pub fn beatmap_file(path: impl AsRef<Path>, error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::NotFound {
+            Self::io("read beatmap", path, error)
+        } else {
+            Self::parse("beatmap", error).with_context(path.as_ref().display().to_string())
+        }
+    }
+
+    pub fn parse(what: &str, error: impl fmt::Display) -> Self {
+        Self::new(
+            ErrorCode::Parse,
+            format!("Failed to parse {}: {}", what, error),
+        )
+    }
+
+    /// A background task that panicked or was cancelled
+    pub fn task(name: &str, error: impl fmt::Display) -> Self {
+        Self::internal(format!("{} task failed: {}", name, error))
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.context {
+            Some(context) => write!(f, "{} ({})", self.message, context),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<CalcError> for AppError {
+    fn from(error: CalcError) -> Self {
+        let message = error.to_string();
+        match error {
+            CalcError::MissingFile { path, .. } => {
+                Self::new(ErrorCode::FileNotFound, message).with_context(path)
+            }
+            CalcError::Parse { path, .. } => {
+                Self::new(ErrorCode::Parse, message).with_context(path)
+            }
+            CalcError::Calc { .. } | CalcError::Cancelled => Self::new(ErrorCode::Calc, message),
+        }
+    }
+}
+
+impl From<rosu_memory_lib::Error> for AppError {
+    fn from(error: rosu_memory_lib::Error) -> Self {
+        Self::new(ErrorCode::Memory, error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(error: reqwest::Error) -> Self {
+        Self::new(ErrorCode::Network, format!("Request failed: {}", error))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::react::{NpsData, Rates};
+use crate::core::react::{BpmStats, DensityData, NpsData, PatternData, Rates};
 use rosu_memory_lib::reader::beatmap::common::BeatmapInfo;
 
 #[derive(Debug, Clone)]
@@ -21,4 +21,8 @@ pub struct CurrentBeatmapData {
     pub beatmap_info: Option<BeatmapInfo>,
     pub rates: Vec<Rates>,
     pub nps_data: Option<NpsData>,
+    pub density_data: Option<DensityData>,
+    pub pattern_data: Option<PatternData>,
+    /// Filled by the calculation job, which already parses the .osu file
+    pub bpm: Option<BpmStats>,
 }
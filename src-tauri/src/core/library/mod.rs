@@ -0,0 +1,147 @@
+use crate::core::error::{AppError, AppResult};
+use crate::core::stable::osu_db::{read_osu_db, OsuDb};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Location of an installed beatmap difficulty relative to the Songs folder
+#[derive(Debug, Clone)]
+pub struct LocalBeatmap {
+    pub folder: String,
+    pub filename: String,
+}
+
+/// Index of installed beatmaps keyed by the md5 of their .osu file
+pub type SongsIndex = HashMap<String, LocalBeatmap>;
+
+struct CachedOsuDb {
+    path: PathBuf,
+    modified: SystemTime,
+    db: Arc<OsuDb>,
+}
+
+lazy_static! {
+    static ref OSU_DB_CACHE: Mutex<Option<CachedOsuDb>> = Mutex::new(None);
+}
+
+/// osu! installation folder, assumed to contain the Songs folder
+pub fn osu_folder(songs_path: &str) -> PathBuf {
+    Path::new(songs_path)
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+/// Load `osu!.db`, reusing the parsed copy until the file changes on disk
+pub fn load_osu_db(songs_path: &str) -> AppResult<Arc<OsuDb>> {
+    let path = osu_folder(songs_path).join("osu!.db");
+    let modified = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| AppError::io("access osu!.db", &path, e))?;
+
+    if let Ok(cache) = OSU_DB_CACHE.lock() {
+        if let Some(cached) = cache.as_ref() {
+            if cached.path == path && cached.modified == modified {
+                return Ok(cached.db.clone());
+            }
+        }
+    }
+
+    let db = Arc::new(read_osu_db(&path)?);
+    println!(
+        "📚 Loaded {} beatmaps of {} from {} (version {})",
+        db.beatmaps.len(),
+        db.player_name,
+        path.display(),
+        db.version
+    );
+
+    if let Ok(mut cache) = OSU_DB_CACHE.lock() {
+        *cache = Some(CachedOsuDb {
+            path,
+            modified,
+            db: db.clone(),
+        });
+    }
+    Ok(db)
+}
+
+/// Build the md5 index of installed beatmaps
+///
+/// Uses `osu!.db` when available and falls back to scanning the Songs folder.
+pub fn songs_index(songs_path: &str) -> AppResult<SongsIndex> {
+    match load_osu_db(songs_path) {
+        Ok(db) => Ok(db
+            .beatmaps
+            .iter()
+            .filter(|beatmap| !beatmap.md5.is_empty())
+            .map(|beatmap| {
+                (
+                    beatmap.md5.clone(),
+                    LocalBeatmap {
+                        folder: beatmap.folder.clone(),
+                        filename: beatmap.filename.clone(),
+                    },
+                )
+            })
+            .collect()),
+        Err(e) => {
+            eprintln!("⚠️  {}, scanning Songs folder instead", e);
+            scan_songs_folder(Path::new(songs_path))
+        }
+    }
+}
+
+/// Resolve the .osu file path of an installed beatmap from its md5
+pub fn resolve_md5(songs_path: &str, md5: &str) -> AppResult<Option<PathBuf>> {
+    let index = songs_index(songs_path)?;
+    Ok(index.get(md5).map(|local| {
+        Path::new(songs_path)
+            .join(&local.folder)
+            .join(&local.filename)
+    }))
+}
+
+/// Build an md5 index by hashing every .osu file of the Songs folder
+pub fn scan_songs_folder(songs_path: &Path) -> AppResult<SongsIndex> {
+    let entries =
+        fs::read_dir(songs_path).map_err(|e| AppError::io("read songs folder", songs_path, e))?;
+
+    let mut index = SongsIndex::new();
+    for folder in entries.flatten() {
+        let folder_path = folder.path();
+        if !folder_path.is_dir() {
+            continue;
+        }
+        let Ok(files) = fs::read_dir(&folder_path) else {
+            continue;
+        };
+        let folder_name = folder.file_name().to_string_lossy().into_owned();
+
+        for file in files.flatten() {
+            let filename = file.file_name().to_string_lossy().into_owned();
+            if !filename.to_lowercase().ends_with(".osu") {
+                continue;
+            }
+            if let Ok(content) = fs::read(file.path()) {
+                index.insert(
+                    format!("{:x}", md5::compute(&content)),
+                    LocalBeatmap {
+                        folder: folder_name.clone(),
+                        filename,
+                    },
+                );
+            }
+        }
+    }
+
+    println!(
+        "📚 Indexed {} beatmaps from {}",
+        index.len(),
+        songs_path.display()
+    );
+    Ok(index)
+}
@@ -1,9 +1,16 @@
 pub mod beatmap;
 pub mod calc;
+pub mod collection;
 pub mod connection;
 pub mod download;
+pub mod error;
 pub mod internal;
+pub mod library;
 pub mod preferences;
+pub mod profile;
+pub mod recommend;
 pub mod react;
+pub mod replay;
 pub mod edit;
-pub mod packmaker;
\ No newline at end of file
+pub mod packmaker;
+pub mod stable;
\ No newline at end of file
@@ -2,9 +2,12 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::core::react::Beatmapset;
+use crate::core::calc::bpm::calculate_bpm_stats;
 use crate::core::internal::CurrentBeatmapData;
-use crate::core::beatmap::serialization::serialize_beatmap;
+use crate::core::beatmap::serialization::{serialize_beatmap, serialize_parsed_beatmap};
+use crate::core::library::LocalBeatmap;
 use crate::core::preferences;
+use crate::core::error::{AppError, AppResult};
 use std::fs;
 use std::str::FromStr;
 use rosu_map::Beatmap as RmBeatmap;
@@ -48,13 +51,13 @@ impl PackMaker {
 pub async fn add_current_to_pack(
     pack: &SharedPackMaker,
     current: &tokio::sync::MutexGuard<'_, CurrentBeatmapData>,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let beatmap_info = current
         .beatmap_info
         .clone()
-        .ok_or_else(|| "No current beatmap".to_string())?;
+        .ok_or_else(AppError::no_beatmap)?;
     // Load songs path from preferences
-    let songs_path = preferences::load_config().songs_path;
+    let songs_path = preferences::songs_path();
     // Build full osu file path
     let osu_path = format!(
         "{}/{}/{}",
@@ -62,10 +65,11 @@ pub async fn add_current_to_pack(
     );
     // Load and parse the .osu file similar to calc
     let osu_map_string = fs::read_to_string(&osu_path)
-        .map_err(|e| format!("Failed to read osu file: {}", e))?;
+        .map_err(|e| AppError::io("read osu file", &osu_path, e))?;
     let parsed = RmBeatmap::from_str(&osu_map_string)
-        .map_err(|e| format!("Failed to parse osu file: {}", e))?;
-    let beatmapset: Beatmapset = serialize_beatmap(&beatmap_info, &songs_path);
+        .map_err(|e| AppError::parse("osu file", e).with_context(osu_path.clone()))?;
+    let mut beatmapset: Beatmapset = serialize_beatmap(&beatmap_info, &songs_path);
+    beatmapset.beatmaps[0].bpm = Some(calculate_bpm_stats(&parsed));
 
     // Save the cover image as base64
     let cover_image = beatmapset.cover_url.clone();
@@ -88,6 +92,30 @@ pub async fn add_current_to_pack(
     Ok(())
 }
 
+/// Add an installed beatmap file to the pack
+pub async fn add_local_beatmap_to_pack(
+    pack: &SharedPackMaker,
+    songs_path: &str,
+    local: &LocalBeatmap,
+) -> AppResult<()> {
+    let osu_path = format!("{}/{}/{}", songs_path, local.folder, local.filename);
+    let osu_map_string = fs::read_to_string(&osu_path)
+        .map_err(|e| AppError::io("read osu file", &osu_path, e))?;
+    let parsed = RmBeatmap::from_str(&osu_map_string)
+        .map_err(|e| AppError::parse("osu file", e).with_context(&osu_path))?;
+    let beatmapset = serialize_parsed_beatmap(&parsed, songs_path, &local.folder);
+    let cover_image = beatmapset.cover_url.clone();
+
+    let mut guard = pack.lock().await;
+    guard.beatmaps.push(BeatmapData {
+        beatmap: beatmapset,
+        rm_beatmap: Some(parsed),
+        cover_image,
+        modifications: PackBeatmapModifications::default(),
+    });
+    Ok(())
+}
+
 pub async fn get_public_pack(pack: &SharedPackMaker) -> (PackMetadata, Vec<PublicBeatmapData>) {
     let guard = pack.lock().await;
     let meta = guard.metadata.clone();
@@ -108,30 +136,30 @@ pub async fn update_pack_beatmap_version(
     pack: &SharedPackMaker,
     index: usize,
     new_version: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let mut guard = pack.lock().await;
     let item = guard
         .beatmaps
         .get_mut(index)
-        .ok_or_else(|| "Invalid beatmap index".to_string())?;
+        .ok_or_else(|| AppError::invalid_input("Invalid beatmap index"))?;
 
     // Update rm_beatmap by editing the version field
     if let Some(ref mut rm) = item.rm_beatmap {
         // Encode, replace Version: line, and re-parse to ensure consistency
         let osu_text = rm
             .encode_to_string()
-            .map_err(|e| format!("Failed to encode beatmap: {}", e))?;
+            .map_err(|e| AppError::internal(format!("Failed to encode beatmap: {}", e)))?;
         // Replace Version: ... line (difficulty name)
         let replaced = replace_version_line(&osu_text, &new_version);
         let reparsed = RmBeatmap::from_str(&replaced)
-            .map_err(|e| format!("Failed to reparse beatmap after version update: {}", e))?;
+            .map_err(|e| AppError::parse("beatmap after version update", e))?;
         *rm = reparsed;
         // Update the display Beatmapset difficulty name too
         if let Some(first) = item.beatmap.beatmaps.get_mut(0) {
             first.name = new_version.clone();
         }
     } else {
-        return Err("No rm_beatmap stored for this item".to_string());
+        return Err(AppError::internal("No rm_beatmap stored for this item"));
     }
 
     Ok(())
@@ -153,7 +181,7 @@ fn replace_version_line(osu_text: &str, new_version: &str) -> String {
     out
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct PackBeatmapModifications {
     pub od: Option<f32>,
     pub hp: Option<f32>,
@@ -168,7 +196,7 @@ pub async fn update_pack_beatmap(
     pack: &SharedPackMaker,
     index: usize,
     modifications: PackBeatmapModifications,
-) -> Result<(), String> {
+) -> AppResult<()> {
     use crate::core::edit::rates::rates::rate;
     use crate::core::edit::ln::ln::full_ln;
     use crate::core::edit::ln::noln::noln;
@@ -178,17 +206,17 @@ pub async fn update_pack_beatmap(
     let item = guard
         .beatmaps
         .get_mut(index)
-        .ok_or_else(|| "Invalid beatmap index".to_string())?;
+        .ok_or_else(|| AppError::invalid_input("Invalid beatmap index"))?;
 
     let rm = item
         .rm_beatmap
         .as_mut()
-        .ok_or_else(|| "No rm_beatmap stored for this item".to_string())?;
+        .ok_or_else(|| AppError::internal("No rm_beatmap stored for this item"))?;
 
     // Encode to text to allow version name replacement; keep a working copy
     let mut osu_text = rm
         .encode_to_string()
-        .map_err(|e| format!("Failed to encode beatmap: {}", e))?;
+        .map_err(|e| AppError::internal(format!("Failed to encode beatmap: {}", e)))?;
 
     // Apply OD
     if let Some(od) = modifications.od {
@@ -236,7 +264,7 @@ pub async fn update_pack_beatmap(
     if let Some(name) = modifications.version_name {
         osu_text = replace_version_line(&osu_text, &name);
         *rm = RmBeatmap::from_str(&osu_text)
-            .map_err(|e| format!("Failed to reparse beatmap after version update: {}", e))?;
+            .map_err(|e| AppError::parse("beatmap after version update", e))?;
         if let Some(first) = item.beatmap.beatmaps.get_mut(0) {
             first.name = name;
         }
@@ -250,12 +278,12 @@ pub async fn update_pack_beatmap_modifications(
     pack: &SharedPackMaker,
     index: usize,
     modifications: PackBeatmapModifications,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let mut guard = pack.lock().await;
     let item = guard
         .beatmaps
         .get_mut(index)
-        .ok_or_else(|| "Invalid beatmap index".to_string())?;
+        .ok_or_else(|| AppError::invalid_input("Invalid beatmap index"))?;
 
     // Mettre à jour les modifications (sans les appliquer)
     item.modifications = modifications;
@@ -267,12 +295,12 @@ pub async fn update_pack_beatmap_modifications(
 pub async fn apply_pack_beatmap_modifications(
     pack: &SharedPackMaker,
     index: usize,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let mut guard = pack.lock().await;
     let item = guard
         .beatmaps
         .get_mut(index)
-        .ok_or_else(|| "Invalid beatmap index".to_string())?;
+        .ok_or_else(|| AppError::invalid_input("Invalid beatmap index"))?;
 
     // Appliquer les modifications en attente
     update_pack_beatmap(pack, index, item.modifications.clone()).await?;
@@ -295,12 +323,12 @@ pub async fn apply_pack_beatmap_modifications(
 pub async fn get_pack_beatmap_modifications(
     pack: &SharedPackMaker,
     index: usize,
-) -> Result<PackBeatmapModifications, String> {
+) -> AppResult<PackBeatmapModifications> {
     let guard = pack.lock().await;
     let item = guard
         .beatmaps
         .get(index)
-        .ok_or_else(|| "Invalid beatmap index".to_string())?;
+        .ok_or_else(|| AppError::invalid_input("Invalid beatmap index"))?;
 
     Ok(item.modifications.clone())
 }
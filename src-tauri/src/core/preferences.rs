@@ -1,30 +1,57 @@
+use crate::core::error::{AppError, AppResult};
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Placeholder Songs path until the folder is set or detected from osu!
+const DEFAULT_SONGS_PATH: &str = "Songs";
+
+lazy_static! {
+    /// Songs folder of the last attached osu! client
+    static ref DETECTED_SONGS_PATH: Mutex<Option<String>> = Mutex::new(None);
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppConfig {
     pub songs_path: String,
     pub theme: String,
+    /// Append generated maps to an osu! collection (opt-in)
+    #[serde(default)]
+    pub write_generated_to_collection: bool,
+    #[serde(default = "default_generated_collection_name")]
+    pub generated_collection_name: String,
+}
+
+fn default_generated_collection_name() -> String {
+    "osef.me generated".to_string()
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
-            songs_path: "Songs".to_string(),
+            songs_path: DEFAULT_SONGS_PATH.to_string(),
             theme: "dark".to_string(),
+            write_generated_to_collection: false,
+            generated_collection_name: default_generated_collection_name(),
         }
     }
 }
 
-fn get_config_path() -> PathBuf {
+/// Directory holding the app's config and data files
+pub fn config_dir() -> PathBuf {
     let config_dir = dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("osef-me");
     if !config_dir.exists() {
         let _ = fs::create_dir_all(&config_dir);
     }
-    config_dir.join("config.toml")
+    config_dir
+}
+
+fn get_config_path() -> PathBuf {
+    config_dir().join("config.toml")
 }
 
 pub fn load_config() -> AppConfig {
@@ -39,33 +66,92 @@ pub fn load_config() -> AppConfig {
     AppConfig::default()
 }
 
-pub fn save_config(config: &AppConfig) -> Result<(), String> {
+pub fn save_config(config: &AppConfig) -> AppResult<()> {
     let config_path = get_config_path();
-    let toml_string =
-        toml::to_string_pretty(config).map_err(|e| format!("Failed to serialize config: {}", e))?;
+    let toml_string = toml::to_string_pretty(config)
+        .map_err(|e| AppError::internal(format!("Failed to serialize config: {}", e)))?;
     fs::write(&config_path, toml_string)
-        .map_err(|e| format!("Failed to write config file: {}", e))?;
+        .map_err(|e| AppError::io("write config file", &config_path, e))?;
     Ok(())
 }
 
-pub fn get_songs_path() -> Result<String, String> {
+pub fn get_songs_path() -> AppResult<String> {
     let config = load_config();
     Ok(config.songs_path)
 }
 
-pub fn set_songs_path(path: String) -> Result<(), String> {
+/// A songs path is usable once it points to an existing folder
+pub fn is_valid_songs_path(path: &Path) -> bool {
+    path.is_dir()
+}
+
+/// Remember the Songs folder found for the running client
+pub fn set_detected_songs_path(path: String) {
+    if let Ok(mut detected) = DETECTED_SONGS_PATH.lock() {
+        *detected = Some(path);
+    }
+}
+
+/// Songs folder to read beatmaps from
+///
+/// The configured path, or the running client's folder when the configured one does not exist.
+pub fn songs_path() -> String {
+    let configured = load_config().songs_path;
+    if is_valid_songs_path(Path::new(&configured)) {
+        return configured;
+    }
+    DETECTED_SONGS_PATH
+        .lock()
+        .ok()
+        .and_then(|detected| detected.clone())
+        .unwrap_or(configured)
+}
+
+pub fn set_songs_path(path: String) -> AppResult<()> {
     let mut config = load_config();
+    // The default placeholder is accepted as is, it is resolved once osu! is detected
+    if path != DEFAULT_SONGS_PATH && !is_valid_songs_path(Path::new(&path)) {
+        return Err(
+            AppError::invalid_input("Songs folder does not exist or is not a folder")
+                .with_context(path),
+        );
+    }
     config.songs_path = path;
     save_config(&config)
 }
 
-pub fn get_theme() -> Result<String, String> {
+pub fn get_theme() -> AppResult<String> {
     let config = load_config();
     Ok(config.theme)
 }
 
-pub fn set_theme(theme: String) -> Result<(), String> {
+pub fn set_theme(theme: String) -> AppResult<()> {
     let mut config = load_config();
     config.theme = theme;
     save_config(&config)
 }
+
+pub fn get_write_generated_to_collection() -> AppResult<bool> {
+    let config = load_config();
+    Ok(config.write_generated_to_collection)
+}
+
+pub fn set_write_generated_to_collection(enabled: bool) -> AppResult<()> {
+    let mut config = load_config();
+    config.write_generated_to_collection = enabled;
+    save_config(&config)
+}
+
+pub fn get_generated_collection_name() -> AppResult<String> {
+    let config = load_config();
+    Ok(config.generated_collection_name)
+}
+
+pub fn set_generated_collection_name(name: String) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::invalid_input("Collection name cannot be empty"));
+    }
+    let mut config = load_config();
+    config.generated_collection_name = name;
+    save_config(&config)
+}
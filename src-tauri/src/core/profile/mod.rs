@@ -0,0 +1,197 @@
+pub mod play;
+
+use crate::core::calc::wife::erfc;
+use crate::core::error::{AppError, AppResult};
+use crate::core::preferences;
+use crate::core::react::ManiaRating;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Best scores per skillset that count towards the player rating
+const TOP_SCORES: usize = 250;
+/// Etterna scales each aggregated skillset rating of the player by this
+const PLAYER_RATING_MULTIPLIER: f64 = 1.05;
+/// Maximum number of rating snapshots kept in the history
+const MAX_HISTORY: usize = 1000;
+/// Plays kept in the profile, beyond it old plays that do not count towards the rating are dropped
+const MAX_PLAYS: usize = 5000;
+
+lazy_static! {
+    static ref PROFILE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// A completed osu!mania play rated like an Etterna score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayRecord {
+    /// Unix timestamp in seconds
+    pub played_at: u64,
+    pub beatmap_md5: String,
+    pub beatmap_name: String,
+    pub centirate: i32,
+    pub wife_percent: f64,
+    pub overall: f64,
+    pub skillsets: ManiaRating,
+}
+
+/// Aggregated player rating per skillset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillsetProfile {
+    pub overall: f64,
+    pub skillsets: ManiaRating,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileSnapshot {
+    /// Unix timestamp in seconds
+    pub recorded_at: u64,
+    pub rating: SkillsetProfile,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub rating: Option<SkillsetProfile>,
+    pub plays: Vec<PlayRecord>,
+    pub history: Vec<ProfileSnapshot>,
+}
+
+fn get_profile_path() -> PathBuf {
+    preferences::config_dir().join("profile.json")
+}
+
+pub fn load_profile() -> PlayerProfile {
+    let profile_path = get_profile_path();
+    if profile_path.exists() {
+        if let Ok(contents) = fs::read_to_string(&profile_path) {
+            if let Ok(profile) = serde_json::from_str::<PlayerProfile>(&contents) {
+                return profile;
+            }
+        }
+    }
+    PlayerProfile::default()
+}
+
+fn save_profile(profile: &PlayerProfile) -> AppResult<()> {
+    let json = serde_json::to_string(profile)
+        .map_err(|e| AppError::internal(format!("Failed to serialize profile: {}", e)))?;
+    let path = get_profile_path();
+    fs::write(&path, json).map_err(|e| AppError::io("write profile", &path, e))
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Store a play, recompute the player rating and snapshot it when it changed
+pub fn record_play(play: PlayRecord) -> AppResult<PlayerProfile> {
+    let _guard = PROFILE_LOCK
+        .lock()
+        .map_err(|e| AppError::internal(format!("Failed to lock profile: {}", e)))?;
+
+    let mut profile = load_profile();
+    profile.plays.push(play);
+    trim_plays(&mut profile.plays);
+    let rating = compute_rating(&profile.plays);
+
+    let changed = profile.rating.as_ref().map(|r| r.overall) != Some(rating.overall);
+    if changed {
+        profile.history.push(ProfileSnapshot {
+            recorded_at: unix_now(),
+            rating: rating.clone(),
+        });
+        if profile.history.len() > MAX_HISTORY {
+            let excess = profile.history.len() - MAX_HISTORY;
+            profile.history.drain(..excess);
+        }
+    }
+    profile.rating = Some(rating);
+
+    save_profile(&profile)?;
+    Ok(profile)
+}
+
+/// Drop the oldest plays above `MAX_PLAYS`, keeping every chart's best play per skillset
+///
+/// The kept plays are the only ones `compute_rating` reads, so trimming never changes the rating.
+fn trim_plays(plays: &mut Vec<PlayRecord>) {
+    if plays.len() <= MAX_PLAYS {
+        return;
+    }
+
+    let mut best: HashMap<(&str, usize), usize> = HashMap::new();
+    for (index, play) in plays.iter().enumerate() {
+        for (skillset, value) in play.skillsets.values().into_iter().enumerate() {
+            let entry = best
+                .entry((play.beatmap_md5.as_str(), skillset))
+                .or_insert(index);
+            if value > plays[*entry].skillsets.values()[skillset] {
+                *entry = index;
+            }
+        }
+    }
+    let keep: HashSet<usize> = best.into_values().collect();
+
+    let mut excess = plays.len() - MAX_PLAYS;
+    let mut index = 0;
+    plays.retain(|_| {
+        let drop = excess > 0 && !keep.contains(&index);
+        if drop {
+            excess -= 1;
+        }
+        index += 1;
+        !drop
+    });
+}
+
+/// Compute per-skillset ratings from the best score of each chart
+pub fn compute_rating(plays: &[PlayRecord]) -> SkillsetProfile {
+    let mut ratings = [0.0; 7];
+    for (skillset, rating) in ratings.iter_mut().enumerate() {
+        let mut best: HashMap<&str, f64> = HashMap::new();
+        for play in plays {
+            let value = play.skillsets.values()[skillset];
+            let entry = best.entry(play.beatmap_md5.as_str()).or_insert(value);
+            *entry = entry.max(value);
+        }
+        let mut scores: Vec<f64> = best.into_values().collect();
+        scores.sort_by(|a, b| b.total_cmp(a));
+        scores.truncate(TOP_SCORES);
+        *rating = aggregate(&scores) * PLAYER_RATING_MULTIPLIER;
+    }
+
+    SkillsetProfile {
+        overall: aggregate(&ratings),
+        skillsets: ManiaRating::from_values(ratings),
+    }
+}
+
+/// Etterna's rating aggregation: the highest rating the scores can "pay for"
+fn aggregate(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut rating = 0.0;
+    let mut resolution = 10.24;
+    for _ in 0..11 {
+        loop {
+            rating += resolution;
+            let sum: f64 = values
+                .iter()
+                .map(|v| (2.0 / erfc(0.1 * (v - rating)) - 2.0).max(0.0))
+                .sum();
+            if 2f64.powf(rating * 0.1) >= sum {
+                break;
+            }
+        }
+        rating -= resolution;
+        resolution /= 2.0;
+    }
+    rating + 2.0 * resolution
+}
@@ -0,0 +1,134 @@
+use super::{unix_now, PlayRecord};
+use crate::core::calc::wife::{rescore, score_rating};
+use crate::core::error::{AppError, AppResult};
+use crate::core::replay::judgement::{clock_rate, HitWindows};
+use crate::core::replay::JudgementCounts;
+use minacalc_rs::Calc;
+use rosu_map::Beatmap as RmBeatmap;
+use rosu_mem::process::Process;
+use rosu_memory_lib::reader::beatmap::common::BeatmapInfo;
+use rosu_memory_lib::reader::common::stable::memory::{game_state, is_watching_replay};
+use rosu_memory_lib::reader::common::{GameMode, GameState};
+use rosu_memory_lib::reader::gameplay::stable::memory::{hits, max_combo, mods};
+use rosu_memory_lib::reader::resultscreen::stable::memory::info as result_screen_info;
+use rosu_memory_lib::reader::structs::State;
+use rosu_pp::any::ScoreState;
+
+/// Follows the game state to detect plays reaching the result screen
+#[derive(Debug, Default)]
+pub struct PlayTracker {
+    last_state: GameState,
+    /// Mods of the play in progress, `None` while spectating a replay
+    mods: Option<u32>,
+}
+
+impl PlayTracker {
+    /// Poll the game state, returning the mods of a play that just finished
+    pub fn poll(&mut self, process: &Process, state: &mut State) -> Option<u32> {
+        let current = game_state(process, state).ok()?;
+        let mut finished = None;
+
+        match current {
+            GameState::Playing => {
+                self.mods = if is_watching_replay(process, state).unwrap_or(false) {
+                    None
+                } else {
+                    mods(process, state).ok().or(self.mods)
+                };
+            }
+            GameState::ResultScreen if self.last_state == GameState::Playing => {
+                finished = self.mods.take();
+            }
+            // Quitting or failing leaves gameplay without a result screen
+            _ => self.mods = None,
+        }
+
+        self.last_state = current;
+        finished
+    }
+
+    /// Mods of the play in progress, `None` outside gameplay or while spectating a replay
+    pub fn playing_mods(&self) -> Option<u32> {
+        (self.last_state == GameState::Playing)
+            .then_some(self.mods)
+            .flatten()
+    }
+}
+
+/// Judgements and combo of the play in progress
+pub fn live_score(process: &Process, state: &mut State) -> AppResult<ScoreState> {
+    let hits = hits(process, state)?;
+    let max_combo = max_combo(process, state)?;
+
+    Ok(ScoreState {
+        max_combo: max_combo.max(0) as u32,
+        n_geki: hits._geki.max(0) as u32,
+        n300: hits._300.max(0) as u32,
+        n_katu: hits._katu.max(0) as u32,
+        n100: hits._100.max(0) as u32,
+        n50: hits._50.max(0) as u32,
+        misses: hits._miss.max(0) as u32,
+        ..ScoreState::default()
+    })
+}
+
+/// Judgements of a play read from the result screen, rated later off the monitor
+#[derive(Debug, Clone)]
+pub struct FinishedPlay {
+    pub counts: JudgementCounts,
+    pub mods: u32,
+}
+
+/// Read the play shown on the result screen
+pub fn read_finished_play(
+    process: &Process,
+    state: &mut State,
+    mods: u32,
+) -> AppResult<FinishedPlay> {
+    let result = result_screen_info(process, state)?;
+    if result.mode != GameMode::Mania {
+        return Err(AppError::invalid_input("Not an osu!mania play"));
+    }
+
+    Ok(FinishedPlay {
+        counts: JudgementCounts {
+            max: result.hits._geki.max(0) as u32,
+            perfect: result.hits._300.max(0) as u32,
+            great: result.hits._katu.max(0) as u32,
+            good: result.hits._100.max(0) as u32,
+            bad: result.hits._50.max(0) as u32,
+            miss: result.hits._miss.max(0) as u32,
+        },
+        mods,
+    })
+}
+
+/// Rate a finished play against its beatmap
+///
+/// Hit errors are not available from memory, so Wife3 is estimated from the judgement counts.
+pub fn rate_play(
+    calc: &Calc,
+    beatmap_info: &BeatmapInfo,
+    osu_path: &str,
+    play: &FinishedPlay,
+) -> AppResult<PlayRecord> {
+    let beatmap =
+        RmBeatmap::from_path(osu_path).map_err(|e| AppError::beatmap_file(osu_path, e))?;
+    let windows = HitWindows::from_od(beatmap.overall_difficulty, play.mods);
+    let wife = rescore(&play.counts.estimated_offsets(&windows), 0);
+    let rating = score_rating(calc, &beatmap, clock_rate(play.mods), wife.j4)?;
+
+    let metadata = &beatmap_info.metadata;
+    Ok(PlayRecord {
+        played_at: unix_now(),
+        beatmap_md5: beatmap_info.technical.md5.clone(),
+        beatmap_name: format!(
+            "{} - {} [{}]",
+            metadata.author, metadata.title_romanized, metadata.difficulty
+        ),
+        centirate: rating.centirate,
+        wife_percent: rating.wife_percent,
+        overall: rating.overall,
+        skillsets: rating.skillsets,
+    })
+}
@@ -1,3 +1,4 @@
+use super::bpm_data::BpmStats;
 use super::rating_info::RatingInfo;
 use serde::{Deserialize, Serialize};
 
@@ -11,6 +12,8 @@ pub struct BeatmapInfo {
     pub od: f64,
     pub hp: f64,
     pub ratings: Vec<RatingInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<BpmStats>,
 }
 
 impl Default for BeatmapInfo {
@@ -24,6 +27,7 @@ impl Default for BeatmapInfo {
             od: 8.0,
             hp: 7.5,
             ratings: vec![],
+            bpm: None,
         }
     }
 }
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BpmPoint {
+    /// Seconds from the start of the song
+    pub time: f64,
+    pub bpm: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BpmStats {
+    pub min: f64,
+    pub max: f64,
+    /// BPM under which the most notes are played
+    pub dominant: f64,
+    pub series: Vec<BpmPoint>,
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DensityData {
+    pub key_count: usize,
+    pub drain_time: f64,
+    /// One NPS curve per column, sampled like `NpsData::nps_graph`
+    pub column_nps: Vec<Vec<f64>>,
+    pub left_hand_nps: Vec<f64>,
+    pub right_hand_nps: Vec<f64>,
+    /// Number of rows per chord size, index 0 being single notes
+    pub chord_distribution: Vec<u32>,
+    /// Notes repeating the column of the previous row, per column
+    pub column_jacks: Vec<u32>,
+    pub jack_count: u32,
+    /// Share of the columns held by long notes over time (0-1)
+    pub ln_coverage: Vec<f64>,
+    pub peak_density: Vec<PeakDensity>,
+}
+
+/// Highest NPS sustained over a sliding window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeakDensity {
+    pub window: f64,
+    pub nps: f64,
+    /// Window start in seconds from the first note
+    pub start_time: f64,
+}
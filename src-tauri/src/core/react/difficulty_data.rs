@@ -0,0 +1,20 @@
+use super::ManiaRating;
+use serde::{Deserialize, Serialize};
+
+/// Local MSD of one section, times in seconds from the first note (scaled by the rate)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultySection {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub overall: f64,
+    pub skillsets: ManiaRating,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DifficultyTimeline {
+    pub centirate: i32,
+    /// Length of the chart slice rated for each section, in seconds
+    pub window_seconds: f64,
+    pub drain_time: f64,
+    pub sections: Vec<DifficultySection>,
+}
@@ -0,0 +1,11 @@
+use super::Beatmapset;
+use crate::core::collection::CollectionWrite;
+use serde::Serialize;
+
+/// A beatmap written by the editor
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedBeatmap {
+    pub beatmapset: Beatmapset,
+    /// Set when generated maps are added to an osu! collection
+    pub collection: Option<CollectionWrite>,
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// pp of the play in progress, counting only the objects judged so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LivePerformance {
+    pub centirate: i32,
+    pub stars: f64,
+    pub pp: f64,
+    pub passed_objects: u32,
+}
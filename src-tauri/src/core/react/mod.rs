@@ -1,13 +1,27 @@
 mod beatmap_info;
 mod beatmap_modifications;
 mod beatmapset;
+mod bpm_data;
+mod density_data;
+mod difficulty_data;
+mod generated_beatmap;
+mod live_performance;
 mod nps_data;
+mod pattern_data;
 mod rates;
 mod rating_info;
 
 pub use beatmap_info::BeatmapInfo;
 pub use beatmap_modifications::BeatmapModifications;
 pub use beatmapset::Beatmapset;
+pub use bpm_data::{BpmPoint, BpmStats};
+pub use density_data::{DensityData, PeakDensity};
+pub use difficulty_data::{DifficultySection, DifficultyTimeline};
+pub use generated_beatmap::GeneratedBeatmap;
+pub use live_performance::LivePerformance;
 pub use nps_data::NpsData;
-pub use rates::{ManiaRating, ModeRating, Rates, Rating};
+pub use pattern_data::{PatternData, PatternKind, PatternSegment, PatternShare};
+pub use rates::{
+    CtbRating, ManiaRating, ModeRating, PpValue, Rates, Rating, StdRating, TaikoRating,
+};
 pub use rating_info::RatingInfo;
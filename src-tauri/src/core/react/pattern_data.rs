@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternKind {
+    Stream,
+    Jumpstream,
+    Handstream,
+    Chordjack,
+    Jacks,
+    Rolls,
+    Trills,
+    Brackets,
+    LnRelease,
+}
+
+/// A stretch of the chart dominated by one pattern, times in seconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternSegment {
+    pub pattern: PatternKind,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub notes: usize,
+}
+
+/// Share of the chart's notes covered by a pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternShare {
+    pub pattern: PatternKind,
+    pub notes: usize,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternData {
+    pub segments: Vec<PatternSegment>,
+    /// Sorted by share, most common first
+    pub summary: Vec<PatternShare>,
+}
@@ -4,8 +4,6 @@ use serde::{Deserialize, Serialize};
 pub struct Rates {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub id: Option<i32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub osu_hash: Option<String>,
     pub centirate: i32,
     pub drain_time: f64,
     pub total_time: f64,
@@ -22,15 +20,26 @@ pub struct Rating {
     pub rating: f64,
     pub rating_type: String,
     pub mode_rating: ModeRating,
+    /// rosu-pp performance at set accuracies, only for osu ratings
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pp: Vec<PpValue>,
+}
+
+/// pp awarded for a play at the given accuracy (in percent)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PpValue {
+    pub accuracy: f64,
+    pub pp: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
+#[allow(non_snake_case)]
 pub enum ModeRating {
     Mania { Mania: ManiaRating },
-    Std,
-    Ctb,
-    Taiko,
+    Std { Std: StdRating },
+    Ctb { Ctb: CtbRating },
+    Taiko { Taiko: TaikoRating },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,11 +55,99 @@ pub struct ManiaRating {
     pub technical: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StdRating {
+    pub aim: f64,
+    pub speed: f64,
+    pub flashlight: f64,
+    pub slider_factor: f64,
+    pub speed_note_count: f64,
+    pub ar: f64,
+    pub max_combo: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaikoRating {
+    pub stamina: f64,
+    pub rhythm: f64,
+    pub color: f64,
+    pub reading: f64,
+    pub max_combo: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CtbRating {
+    pub ar: f64,
+    pub fruits: u32,
+    pub droplets: u32,
+    pub tiny_droplets: u32,
+    pub max_combo: u32,
+}
+
+impl ManiaRating {
+    /// Skillset names, in the order returned by `values`
+    pub const SKILLSETS: [&'static str; 7] = [
+        "stream",
+        "jumpstream",
+        "handstream",
+        "stamina",
+        "jackspeed",
+        "chordjack",
+        "technical",
+    ];
+
+    pub fn values(&self) -> [f64; 7] {
+        [
+            self.stream,
+            self.jumpstream,
+            self.handstream,
+            self.stamina,
+            self.jackspeed,
+            self.chordjack,
+            self.technical,
+        ]
+    }
+
+    pub fn from_values(values: [f64; 7]) -> Self {
+        let [stream, jumpstream, handstream, stamina, jackspeed, chordjack, technical] = values;
+        Self {
+            id: None,
+            stream,
+            jumpstream,
+            handstream,
+            stamina,
+            jackspeed,
+            chordjack,
+            technical,
+        }
+    }
+
+    /// Value of a skillset by name
+    pub fn get(&self, skillset: &str) -> Option<f64> {
+        Self::SKILLSETS
+            .iter()
+            .position(|name| *name == skillset)
+            .map(|index| self.values()[index])
+    }
+}
+
+impl Rates {
+    /// Etterna overall rating and skillsets, if calculated
+    pub fn etterna(&self) -> Option<(f64, &ManiaRating)> {
+        self.rating
+            .iter()
+            .filter(|rating| rating.rating_type == "etterna")
+            .find_map(|rating| match &rating.mode_rating {
+                ModeRating::Mania { Mania } => Some((rating.rating, Mania)),
+                _ => None,
+            })
+    }
+}
+
 impl Default for Rates {
     fn default() -> Self {
         Self {
             id: Some(1),
-            osu_hash: Some("abc123".to_string()),
             centirate: 100,
             drain_time: 120.0,
             total_time: 180.0,
@@ -0,0 +1,344 @@
+use crate::core::beatmap::serialization::serialize_parsed_beatmap;
+use crate::core::calc::error::CalcError;
+use crate::core::calc::etterna::process_beatmap;
+use crate::core::calc::patterns::classify_patterns;
+use crate::core::error::{AppError, AppResult};
+use crate::core::library::load_osu_db;
+use crate::core::preferences;
+use crate::core::profile::load_profile;
+use crate::core::react::{Beatmapset, ManiaRating, PatternKind, Rates};
+use crate::core::stable::osu_db::OsuDbBeatmap;
+use lazy_static::lazy_static;
+use minacalc_rs::Calc;
+use rosu_map::section::hit_objects::HitObjectKind;
+use rosu_map::Beatmap as RmBeatmap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use tauri::{AppHandle, Emitter};
+
+const MODE_MANIA: u8 = 3;
+const DEFAULT_LIMIT: usize = 20;
+/// Default allowed distance between a map's skillset rating and the player's
+const DEFAULT_TOLERANCE: f64 = 1.5;
+/// Minimum share of the overall MSD for a skillset to count as a map's focus
+const MIN_FOCUS: f64 = 0.9;
+/// Maps rated between two `recommend-progress` events
+const PROGRESS_STEP: usize = 20;
+
+lazy_static! {
+    static ref MSD_CACHE: Mutex<Option<HashMap<String, CachedMsd>>> = Mutex::new(None);
+}
+
+/// Rates of a local beatmap, cached by md5 to avoid recalculating the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedMsd {
+    ln_ratio: f64,
+    rates: Vec<Rates>,
+    /// Most common pattern, `None` when the map has no detected pattern
+    #[serde(default)]
+    pattern: Option<PatternKind>,
+    /// Set once the map went through the current analysis, even if it failed.
+    /// Entries written before pattern detection lack it and are analyzed again.
+    #[serde(default)]
+    analyzed: bool,
+}
+
+impl CachedMsd {
+    /// Entry for a map that could not be rated, so it is not retried on every call
+    fn failed() -> Self {
+        Self {
+            ln_ratio: 0.0,
+            rates: Vec::new(),
+            pattern: None,
+            analyzed: true,
+        }
+    }
+}
+
+/// Filters for map recommendations
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecommendOptions {
+    /// Skillset to train, the player's weakest one when omitted
+    pub skillset: Option<String>,
+    pub key_count: Option<u32>,
+    /// Drain time bounds in seconds, after the rate is applied
+    pub min_length: Option<f64>,
+    pub max_length: Option<f64>,
+    /// Long note share of the objects, between 0 and 1
+    pub min_ln_ratio: Option<f64>,
+    pub max_ln_ratio: Option<f64>,
+    /// Only suggest maps dominated by this pattern
+    pub pattern: Option<PatternKind>,
+    pub tolerance: Option<f64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Recommendation {
+    pub beatmapset: Beatmapset,
+    pub rates: Rates,
+    pub skillset: String,
+    /// Player rating the map was matched against
+    pub target: f64,
+    /// Map rating in the skillset at this rate
+    pub difficulty: f64,
+    pub ln_ratio: f64,
+    pub pattern: Option<PatternKind>,
+}
+
+/// Payload of the `recommend-progress` event, sent while the library is being rated
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+struct Candidate<'a> {
+    beatmap: &'a OsuDbBeatmap,
+    rates: Rates,
+    difficulty: f64,
+    ln_ratio: f64,
+    pattern: Option<PatternKind>,
+}
+
+fn get_cache_path() -> PathBuf {
+    preferences::config_dir().join("msd_cache.json")
+}
+
+fn load_cache() -> HashMap<String, CachedMsd> {
+    fs::read_to_string(get_cache_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn lock_cache() -> AppResult<MutexGuard<'static, Option<HashMap<String, CachedMsd>>>> {
+    MSD_CACHE
+        .lock()
+        .map_err(|e| AppError::internal(format!("Failed to lock MSD cache: {}", e)))
+}
+
+fn save_cache(cache: &HashMap<String, CachedMsd>) -> AppResult<()> {
+    let json = serde_json::to_string(cache)
+        .map_err(|e| AppError::internal(format!("Failed to serialize MSD cache: {}", e)))?;
+    let path = get_cache_path();
+    fs::write(&path, json).map_err(|e| AppError::io("write MSD cache", &path, e))
+}
+
+fn ln_ratio(beatmap: &RmBeatmap) -> f64 {
+    if beatmap.hit_objects.is_empty() {
+        return 0.0;
+    }
+    let holds = beatmap
+        .hit_objects
+        .iter()
+        .filter(|object| matches!(object.kind, HitObjectKind::Hold(_)))
+        .count();
+    holds as f64 / beatmap.hit_objects.len() as f64
+}
+
+/// Pick the skillset to train: the requested one, or the player's weakest
+fn target_skillset(options: &RecommendOptions, rating: &ManiaRating) -> AppResult<(String, f64)> {
+    if let Some(skillset) = &options.skillset {
+        let value = rating
+            .get(skillset)
+            .ok_or_else(|| AppError::invalid_input(format!("Unknown skillset '{}'", skillset)))?;
+        return Ok((skillset.clone(), value));
+    }
+
+    ManiaRating::SKILLSETS
+        .iter()
+        .zip(rating.values())
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(name, value)| (name.to_string(), value))
+        .ok_or_else(|| AppError::internal("No skillsets available"))
+}
+
+fn matches_metadata(beatmap: &OsuDbBeatmap, options: &RecommendOptions) -> bool {
+    beatmap.mode == MODE_MANIA
+        && options
+            .key_count
+            .is_none_or(|keys| beatmap.cs.round() as u32 == keys)
+}
+
+/// Best rate of a cached map for the target, if any rate fits the filters
+fn best_rate(
+    cached: &CachedMsd,
+    skillset: &str,
+    target: f64,
+    tolerance: f64,
+    options: &RecommendOptions,
+) -> Option<(Rates, f64)> {
+    if options
+        .min_ln_ratio
+        .is_some_and(|min| cached.ln_ratio < min)
+        || options
+            .max_ln_ratio
+            .is_some_and(|max| cached.ln_ratio > max)
+    {
+        return None;
+    }
+    if options.pattern.is_some() && cached.pattern != options.pattern {
+        return None;
+    }
+
+    cached
+        .rates
+        .iter()
+        .filter(|rates| options.min_length.is_none_or(|min| rates.drain_time >= min))
+        .filter(|rates| options.max_length.is_none_or(|max| rates.drain_time <= max))
+        .filter_map(|rates| {
+            let (overall, skillsets) = rates.etterna()?;
+            let difficulty = skillsets.get(skillset)?;
+            let focused = overall > 0.0 && difficulty / overall >= MIN_FOCUS;
+            (focused && (difficulty - target).abs() <= tolerance)
+                .then(|| (rates.clone(), difficulty))
+        })
+        .min_by(|a, b| (a.1 - target).abs().total_cmp(&(b.1 - target).abs()))
+}
+
+/// Calculate and cache the rates of every candidate map missing from the cache
+fn fill_cache(
+    app_handle: &AppHandle,
+    cache: &mut HashMap<String, CachedMsd>,
+    beatmaps: &[&OsuDbBeatmap],
+    songs_path: &str,
+) -> AppResult<()> {
+    let missing: Vec<&&OsuDbBeatmap> = beatmaps
+        .iter()
+        .filter(|beatmap| {
+            cache
+                .get(&beatmap.md5)
+                .is_none_or(|cached| !cached.analyzed)
+        })
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    println!("🧮 Calculating MSD for {} local maps...", missing.len());
+    emit_progress(app_handle, 0, missing.len());
+    let calc = Calc::new().map_err(CalcError::calc)?;
+    for (index, beatmap) in missing.iter().enumerate() {
+        let osu_path = Path::new(songs_path)
+            .join(&beatmap.folder)
+            .join(&beatmap.filename);
+        if !osu_path.is_file() {
+            continue;
+        }
+        match process_beatmap(&calc, &osu_path.to_string_lossy()) {
+            Ok((rates, parsed)) => {
+                cache.insert(
+                    beatmap.md5.clone(),
+                    CachedMsd {
+                        ln_ratio: ln_ratio(&parsed),
+                        rates,
+                        pattern: classify_patterns(&parsed)
+                            .summary
+                            .first()
+                            .map(|share| share.pattern),
+                        analyzed: true,
+                    },
+                );
+            }
+            Err(e) => {
+                eprintln!("⚠️  Skipping '{}': {}", osu_path.display(), e);
+                cache.insert(beatmap.md5.clone(), CachedMsd::failed());
+            }
+        }
+        if (index + 1) % 100 == 0 {
+            println!("🧮 {}/{} maps calculated", index + 1, missing.len());
+        }
+        if (index + 1) % PROGRESS_STEP == 0 || index + 1 == missing.len() {
+            emit_progress(app_handle, index + 1, missing.len());
+        }
+    }
+
+    save_cache(cache)
+}
+
+fn emit_progress(app_handle: &AppHandle, done: usize, total: usize) {
+    if let Err(e) = app_handle.emit("recommend-progress", RecommendProgress { done, total }) {
+        eprintln!("Failed to emit recommend progress: {}", e);
+    }
+}
+
+/// Suggest local maps and rates near the player's rating in a skillset
+pub fn recommend_maps(
+    app_handle: &AppHandle,
+    options: RecommendOptions,
+) -> AppResult<Vec<Recommendation>> {
+    let rating = load_profile().rating.ok_or_else(|| {
+        AppError::invalid_input("No player rating yet, play some osu!mania maps first")
+    })?;
+    let (skillset, target) = target_skillset(&options, &rating.skillsets)?;
+    let tolerance = options.tolerance.unwrap_or(DEFAULT_TOLERANCE);
+
+    let songs_path = preferences::songs_path();
+    let osu_db = load_osu_db(&songs_path)?;
+    let beatmaps: Vec<&OsuDbBeatmap> = osu_db
+        .beatmaps
+        .iter()
+        .filter(|beatmap| matches_metadata(beatmap, &options))
+        .collect();
+
+    // Held during the calculation: concurrent calls wait and reuse what this one computed
+    let mut guard = lock_cache()?;
+    let cache = guard.get_or_insert_with(load_cache);
+    let filled = fill_cache(app_handle, cache, &beatmaps, &songs_path);
+
+    let mut candidates: Vec<Candidate> = beatmaps
+        .iter()
+        .filter_map(|beatmap| {
+            let cached = cache.get(&beatmap.md5)?;
+            let (rates, difficulty) = best_rate(cached, &skillset, target, tolerance, &options)?;
+            Some(Candidate {
+                beatmap,
+                rates,
+                difficulty,
+                ln_ratio: cached.ln_ratio,
+                pattern: cached.pattern,
+            })
+        })
+        .collect();
+    drop(guard);
+    filled?;
+
+    candidates.sort_by(|a, b| {
+        (a.difficulty - target)
+            .abs()
+            .total_cmp(&(b.difficulty - target).abs())
+    });
+    candidates.truncate(options.limit.unwrap_or(DEFAULT_LIMIT));
+
+    println!(
+        "✅ {} recommendations for {} around {:.2}",
+        candidates.len(),
+        skillset,
+        target
+    );
+
+    Ok(candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let folder = Path::new(&songs_path).join(&candidate.beatmap.folder);
+            let parsed = RmBeatmap::from_path(folder.join(&candidate.beatmap.filename)).ok()?;
+            Some(Recommendation {
+                beatmapset: serialize_parsed_beatmap(
+                    &parsed,
+                    &songs_path,
+                    &candidate.beatmap.folder,
+                ),
+                rates: candidate.rates,
+                skillset: skillset.clone(),
+                target,
+                difficulty: candidate.difficulty,
+                ln_ratio: candidate.ln_ratio,
+                pattern: candidate.pattern,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,173 @@
+use crate::core::calc::notes::{key_count, mania_notes};
+use crate::core::stable::replay::ReplayFrame;
+use rosu_map::Beatmap as RmBeatmap;
+
+const MOD_EASY: u32 = 1 << 1;
+const MOD_HARD_ROCK: u32 = 1 << 4;
+const MOD_DOUBLE_TIME: u32 = 1 << 6;
+const MOD_HALF_TIME: u32 = 1 << 8;
+const MOD_NIGHTCORE: u32 = 1 << 9;
+/// Shuffles the columns with a seed the replay doesn't store
+pub const MOD_RANDOM: u32 = 1 << 21;
+const MOD_MIRROR: u32 = 1 << 30;
+
+/// Playback rate implied by the speed mods of a play
+pub fn clock_rate(mods: u32) -> f32 {
+    if mods & (MOD_DOUBLE_TIME | MOD_NIGHTCORE) != 0 {
+        1.5
+    } else if mods & MOD_HALF_TIME != 0 {
+        0.75
+    } else {
+        1.0
+    }
+}
+
+/// osu!mania stable hit windows in milliseconds (half-widths)
+#[derive(Debug, Clone, Copy)]
+pub struct HitWindows {
+    pub max: f64,
+    pub w300: f64,
+    pub w200: f64,
+    pub w100: f64,
+    pub w50: f64,
+    pub miss: f64,
+}
+
+impl HitWindows {
+    pub fn from_od(od: f32, mods: u32) -> Self {
+        let od = od as f64;
+        let scale = if mods & MOD_HARD_ROCK != 0 {
+            1.0 / 1.4
+        } else if mods & MOD_EASY != 0 {
+            1.4
+        } else {
+            1.0
+        };
+
+        Self {
+            max: 16.0 * scale,
+            w300: (64.0 - 3.0 * od) * scale,
+            w200: (97.0 - 3.0 * od) * scale,
+            w100: (127.0 - 3.0 * od) * scale,
+            w50: (151.0 - 3.0 * od) * scale,
+            miss: (188.0 - 3.0 * od) * scale,
+        }
+    }
+}
+
+/// Outcome of a single note matched against the replay input
+#[derive(Debug, Clone)]
+pub struct NoteHit {
+    pub column: usize,
+    pub time: f64,
+    /// Press offset in ms (positive = late), `None` when missed
+    pub offset: Option<f64>,
+    /// End time of the long note, if any
+    pub ln_end: Option<f64>,
+    /// Release offset in ms relative to the long note end
+    pub release_offset: Option<f64>,
+}
+
+impl NoteHit {
+    /// Whether this long note was pressed but released before the 100 window
+    pub fn dropped_hold(&self, windows: &HitWindows) -> bool {
+        self.ln_end.is_some()
+            && self.offset.is_some()
+            && self
+                .release_offset
+                .is_none_or(|offset| offset < -windows.w100)
+    }
+}
+
+/// Split the frames into `(press, release)` intervals for each column
+fn key_presses(frames: &[ReplayFrame], key_count: usize) -> Vec<Vec<(f64, f64)>> {
+    let mut presses = vec![Vec::new(); key_count];
+    let mut pressed_at: Vec<Option<f64>> = vec![None; key_count];
+
+    for frame in frames {
+        let mask = frame.x.max(0.0) as u32;
+        for (column, start) in pressed_at.iter_mut().enumerate() {
+            let down = mask & (1 << column) != 0;
+            match (down, *start) {
+                (true, None) => *start = Some(frame.time),
+                (false, Some(press)) => {
+                    presses[column].push((press, frame.time));
+                    *start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let end = frames.last().map(|frame| frame.time).unwrap_or(0.0);
+    for (column, start) in pressed_at.into_iter().enumerate() {
+        if let Some(press) = start {
+            presses[column].push((press, end));
+        }
+    }
+
+    presses
+}
+
+/// Match every note of the beatmap with the replay key presses
+///
+/// Presses earlier than the miss window are ghost taps; the first remaining
+/// press of the column within the 50 window judges the note.
+pub fn match_notes(
+    beatmap: &RmBeatmap,
+    frames: &[ReplayFrame],
+    windows: &HitWindows,
+    mods: u32,
+) -> Vec<NoteHit> {
+    let keys = key_count(beatmap);
+    let mut presses = key_presses(frames, keys);
+    // Mirror flips the playfield, map the keys back to the beatmap columns
+    if mods & MOD_MIRROR != 0 {
+        presses.reverse();
+    }
+
+    let mut notes: Vec<Vec<(f64, Option<f64>)>> = vec![Vec::new(); keys];
+    for note in mania_notes(beatmap) {
+        notes[note.column].push((note.start, note.end));
+    }
+
+    let mut hits = Vec::new();
+    for (column, column_notes) in notes.iter_mut().enumerate() {
+        column_notes.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let column_presses = &presses[column];
+        let mut press_idx = 0;
+
+        for &(time, ln_end) in column_notes.iter() {
+            while press_idx < column_presses.len()
+                && column_presses[press_idx].0 < time - windows.miss
+            {
+                press_idx += 1;
+            }
+
+            let mut hit = NoteHit {
+                column,
+                time,
+                offset: None,
+                ln_end,
+                release_offset: None,
+            };
+
+            if let Some(&(press, release)) = column_presses.get(press_idx) {
+                if press <= time + windows.w50 {
+                    press_idx += 1;
+                    let offset = press - time;
+                    // Pressing inside the miss window but before the 50 window is an early miss
+                    if offset >= -windows.w50 {
+                        hit.offset = Some(offset);
+                        hit.release_offset = ln_end.map(|end| release - end);
+                    }
+                }
+            }
+
+            hits.push(hit);
+        }
+    }
+
+    hits.sort_by(|a, b| a.time.total_cmp(&b.time));
+    hits
+}
@@ -0,0 +1,341 @@
+pub mod judgement;
+
+use crate::core::calc::error::CalcError;
+use crate::core::calc::notes::key_count;
+use crate::core::calc::wife::{rescore, score_rating, ScoreRating, WifeRescore};
+use crate::core::error::{AppError, AppResult, ErrorCode};
+use crate::core::library::{osu_folder, songs_index};
+use crate::core::preferences;
+use crate::core::stable::replay::{read_replay, read_replay_header, Replay};
+use judgement::{clock_rate, match_notes, HitWindows, NoteHit, MOD_RANDOM};
+use minacalc_rs::Calc;
+use rosu_map::section::general::GameMode;
+use rosu_map::Beatmap as RmBeatmap;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Width of a hit error histogram bin in milliseconds
+const HISTOGRAM_BIN_MS: f64 = 5.0;
+const MODE_MANIA: u8 = 3;
+
+/// Local replay paired with its installed beatmap
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplaySummary {
+    pub path: String,
+    pub replay: Replay,
+    /// Path of the .osu file, if the beatmap is installed
+    pub beatmap_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JudgementCounts {
+    pub max: u32,
+    pub perfect: u32,
+    pub great: u32,
+    pub good: u32,
+    pub bad: u32,
+    pub miss: u32,
+}
+
+impl JudgementCounts {
+    /// Representative offsets (window midpoints) for rescoring plays without hit errors
+    pub fn estimated_offsets(&self, windows: &HitWindows) -> Vec<Option<f64>> {
+        let bands = [
+            (self.max, windows.max / 2.0),
+            (self.perfect, (windows.max + windows.w300) / 2.0),
+            (self.great, (windows.w300 + windows.w200) / 2.0),
+            (self.good, (windows.w200 + windows.w100) / 2.0),
+            (self.bad, (windows.w100 + windows.w50) / 2.0),
+        ];
+        bands
+            .iter()
+            .flat_map(|(count, offset)| std::iter::repeat_n(Some(*offset), *count as usize))
+            .chain(std::iter::repeat_n(None, self.miss as usize))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HistogramBin {
+    /// Lower bound of the bin in milliseconds
+    pub offset: f64,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ColumnStats {
+    pub column: usize,
+    pub notes: usize,
+    pub misses: usize,
+    pub mean_offset: f64,
+    pub unstable_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LongNoteStats {
+    pub count: usize,
+    /// Long notes held until the end (released no earlier than the 100 window)
+    pub held: usize,
+    pub hold_accuracy: f64,
+    pub mean_release_offset: f64,
+    pub release_unstable_rate: f64,
+}
+
+/// Per-replay osu!mania statistics
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayAnalysis {
+    pub replay: Replay,
+    pub beatmap_path: String,
+    pub key_count: usize,
+    pub od: f32,
+    /// Judgements recomputed from the replay input
+    pub judgements: JudgementCounts,
+    pub mean_hit_error: f64,
+    pub unstable_rate: f64,
+    pub hit_error_histogram: Vec<HistogramBin>,
+    pub columns: Vec<ColumnStats>,
+    pub long_notes: LongNoteStats,
+}
+
+/// Folders where osu! stable stores replays: exported ones and local scores
+fn replay_folders(songs_path: &str) -> Vec<PathBuf> {
+    let osu = osu_folder(songs_path);
+    vec![osu.join("Replays"), osu.join("Data").join("r")]
+}
+
+/// List local osu!mania replays paired with their beatmaps via md5
+pub fn list_replays() -> AppResult<Vec<ReplaySummary>> {
+    let songs_path = preferences::songs_path();
+    let index = songs_index(&songs_path)?;
+
+    let mut summaries = Vec::new();
+    for folder in replay_folders(&songs_path) {
+        let Ok(entries) = fs::read_dir(&folder) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_replay = path
+                .extension()
+                .map(|ext| ext.eq_ignore_ascii_case("osr"))
+                .unwrap_or(false);
+            if !is_replay {
+                continue;
+            }
+
+            let replay = match read_replay_header(&path) {
+                Ok(replay) if replay.mode == MODE_MANIA => replay,
+                Ok(_) => continue,
+                Err(e) => {
+                    eprintln!("⚠️  {}", e);
+                    continue;
+                }
+            };
+            let beatmap_path = index.get(&replay.beatmap_md5).map(|local| {
+                Path::new(&songs_path)
+                    .join(&local.folder)
+                    .join(&local.filename)
+                    .to_string_lossy()
+                    .into_owned()
+            });
+
+            summaries.push(ReplaySummary {
+                path: path.to_string_lossy().into_owned(),
+                replay,
+                beatmap_path,
+            });
+        }
+    }
+
+    summaries.sort_by_key(|summary| std::cmp::Reverse(summary.replay.timestamp));
+    Ok(summaries)
+}
+
+/// Load a replay together with its parsed beatmap and the path it was found at
+pub(crate) fn load_replay_with_beatmap(
+    replay_path: &str,
+) -> AppResult<(Replay, RmBeatmap, String)> {
+    let replay = read_replay(Path::new(replay_path))?;
+    if replay.mode != MODE_MANIA {
+        return Err(AppError::invalid_input(
+            "Only osu!mania replays can be analyzed",
+        ));
+    }
+    if replay.mods & MOD_RANDOM != 0 {
+        return Err(AppError::invalid_input(
+            "Replays played with Random can't be matched to the beatmap columns",
+        ));
+    }
+
+    let songs_path = preferences::songs_path();
+    let local = songs_index(&songs_path)?
+        .remove(&replay.beatmap_md5)
+        .ok_or_else(|| {
+            AppError::new(
+                ErrorCode::FileNotFound,
+                format!("Beatmap {} is not installed", replay.beatmap_md5),
+            )
+        })?;
+    let beatmap_path = Path::new(&songs_path)
+        .join(&local.folder)
+        .join(&local.filename);
+
+    let content = fs::read_to_string(&beatmap_path)
+        .map_err(|e| AppError::io("read beatmap", &beatmap_path, e))?;
+    let beatmap = RmBeatmap::from_str(&content).map_err(|e| {
+        AppError::parse("beatmap", e).with_context(beatmap_path.display().to_string())
+    })?;
+    if beatmap.mode != GameMode::Mania {
+        return Err(AppError::invalid_input(
+            "Converted beatmaps are not supported",
+        ));
+    }
+
+    Ok((replay, beatmap, beatmap_path.to_string_lossy().into_owned()))
+}
+
+/// Wife3 rescore of a replay with its Etterna-style score rating
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayRescore {
+    pub wife: WifeRescore,
+    pub rating: ScoreRating,
+}
+
+/// Analyze a local osu!mania replay against its beatmap
+pub fn analyze_replay(replay_path: &str) -> AppResult<ReplayAnalysis> {
+    let (replay, beatmap, beatmap_path) = load_replay_with_beatmap(replay_path)?;
+    let windows = HitWindows::from_od(beatmap.overall_difficulty, replay.mods);
+    let hits = match_notes(&beatmap, &replay.frames, &windows, replay.mods);
+    let keys = key_count(&beatmap);
+
+    let offsets: Vec<f64> = hits.iter().filter_map(|hit| hit.offset).collect();
+
+    Ok(ReplayAnalysis {
+        key_count: keys,
+        od: beatmap.overall_difficulty,
+        judgements: judge(&hits, &windows),
+        mean_hit_error: mean(&offsets),
+        unstable_rate: unstable_rate(&offsets),
+        hit_error_histogram: histogram(&offsets),
+        columns: column_stats(&hits, keys),
+        long_notes: long_note_stats(&hits, &windows),
+        replay,
+        beatmap_path,
+    })
+}
+
+fn judge(hits: &[NoteHit], windows: &HitWindows) -> JudgementCounts {
+    let mut counts = JudgementCounts::default();
+    for hit in hits {
+        match hit.offset.map(f64::abs) {
+            Some(error) if error <= windows.max => counts.max += 1,
+            Some(error) if error <= windows.w300 => counts.perfect += 1,
+            Some(error) if error <= windows.w200 => counts.great += 1,
+            Some(error) if error <= windows.w100 => counts.good += 1,
+            Some(_) => counts.bad += 1,
+            None => counts.miss += 1,
+        }
+    }
+    counts
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Unstable rate: ten times the standard deviation of the hit errors
+fn unstable_rate(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let avg = mean(values);
+    let variance = values.iter().map(|v| (v - avg).powi(2)).sum::<f64>() / values.len() as f64;
+    variance.sqrt() * 10.0
+}
+
+fn histogram(offsets: &[f64]) -> Vec<HistogramBin> {
+    let mut bins: BTreeMap<i64, u32> = BTreeMap::new();
+    for offset in offsets {
+        *bins
+            .entry((offset / HISTOGRAM_BIN_MS).floor() as i64)
+            .or_insert(0) += 1;
+    }
+    bins.into_iter()
+        .map(|(bin, count)| HistogramBin {
+            offset: bin as f64 * HISTOGRAM_BIN_MS,
+            count,
+        })
+        .collect()
+}
+
+fn column_stats(hits: &[NoteHit], key_count: usize) -> Vec<ColumnStats> {
+    (0..key_count)
+        .map(|column| {
+            let column_hits: Vec<&NoteHit> =
+                hits.iter().filter(|hit| hit.column == column).collect();
+            let offsets: Vec<f64> = column_hits.iter().filter_map(|hit| hit.offset).collect();
+            ColumnStats {
+                column,
+                notes: column_hits.len(),
+                misses: column_hits.len() - offsets.len(),
+                mean_offset: mean(&offsets),
+                unstable_rate: unstable_rate(&offsets),
+            }
+        })
+        .collect()
+}
+
+fn long_note_stats(hits: &[NoteHit], windows: &HitWindows) -> LongNoteStats {
+    let long_notes: Vec<&NoteHit> = hits.iter().filter(|hit| hit.ln_end.is_some()).collect();
+    let releases: Vec<f64> = long_notes
+        .iter()
+        .filter_map(|hit| hit.release_offset)
+        .collect();
+    let held = releases
+        .iter()
+        .filter(|offset| **offset >= -windows.w100)
+        .count();
+
+    LongNoteStats {
+        count: long_notes.len(),
+        held,
+        hold_accuracy: if long_notes.is_empty() {
+            0.0
+        } else {
+            held as f64 / long_notes.len() as f64 * 100.0
+        },
+        mean_release_offset: mean(&releases),
+        release_unstable_rate: unstable_rate(&releases),
+    }
+}
+
+/// Rescore a local osu!mania replay with Wife3 and rate it against the chart MSD
+pub fn rescore_replay(replay_path: &str) -> AppResult<ReplayRescore> {
+    let (replay, beatmap, _) = load_replay_with_beatmap(replay_path)?;
+    let windows = HitWindows::from_od(beatmap.overall_difficulty, replay.mods);
+    let hits = match_notes(&beatmap, &replay.frames, &windows, replay.mods);
+    let rate = clock_rate(replay.mods);
+
+    // Etterna judges in real time, replay offsets are in map time
+    let offsets: Vec<Option<f64>> = hits
+        .iter()
+        .map(|hit| hit.offset.map(|offset| offset / rate as f64))
+        .collect();
+    let dropped_holds = hits.iter().filter(|hit| hit.dropped_hold(&windows)).count();
+    let wife = rescore(&offsets, dropped_holds);
+
+    let calc = Calc::new().map_err(CalcError::calc)?;
+    let rating = score_rating(&calc, &beatmap, rate, wife.j4)?;
+    println!(
+        "🧮 Rescored replay: {:.2}% (J4), {:.2} SSR at {:.2}x",
+        wife.j4, rating.overall, rate
+    );
+
+    Ok(ReplayRescore { wife, rating })
+}
@@ -0,0 +1,92 @@
+use super::reader::OsuReader;
+use super::writer::OsuWriter;
+use crate::core::error::{AppError, AppResult};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// A named list of beatmaps identified by md5
+#[derive(Debug, Clone, Serialize)]
+pub struct Collection {
+    pub name: String,
+    pub beatmaps: Vec<CollectionBeatmap>,
+}
+
+/// A beatmap referenced by a collection
+///
+/// `collection.db` only stores the md5; osdb exports also carry the online IDs.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionBeatmap {
+    pub md5: String,
+    pub beatmap_id: Option<i32>,
+    pub beatmapset_id: Option<i32>,
+}
+
+impl CollectionBeatmap {
+    pub fn from_md5(md5: String) -> Self {
+        Self {
+            md5,
+            beatmap_id: None,
+            beatmapset_id: None,
+        }
+    }
+}
+
+/// Parsed content of an osu! stable `collection.db`
+#[derive(Debug, Clone)]
+pub struct CollectionDb {
+    pub version: i32,
+    pub collections: Vec<Collection>,
+}
+
+/// Read and parse a `collection.db` file
+pub fn read_collection_db(path: &Path) -> AppResult<CollectionDb> {
+    let data = fs::read(path).map_err(|e| AppError::io("read collection file", path, e))?;
+    parse_collection_db(&data)
+        .map_err(|e| AppError::parse("collection file", e).with_context(path.display().to_string()))
+}
+
+pub fn parse_collection_db(data: &[u8]) -> Result<CollectionDb, String> {
+    let mut reader = OsuReader::new(data);
+    let version = reader.read_i32()?;
+    let count = reader.read_i32()?;
+
+    // Name (at least its marker byte) and beatmap count
+    let mut collections = Vec::with_capacity(reader.capacity_for(count, 5));
+    for _ in 0..count {
+        let name = reader.read_string()?;
+        let beatmap_count = reader.read_i32()?;
+        let mut beatmaps = Vec::with_capacity(reader.capacity_for(beatmap_count, 1));
+        for _ in 0..beatmap_count {
+            beatmaps.push(CollectionBeatmap::from_md5(reader.read_string()?));
+        }
+        collections.push(Collection { name, beatmaps });
+    }
+
+    Ok(CollectionDb {
+        version,
+        collections,
+    })
+}
+
+pub fn encode_collection_db(db: &CollectionDb) -> Vec<u8> {
+    let mut writer = OsuWriter::new();
+    writer.write_i32(db.version);
+    writer.write_i32(db.collections.len() as i32);
+    for collection in &db.collections {
+        writer.write_string(&collection.name);
+        writer.write_i32(collection.beatmaps.len() as i32);
+        for beatmap in &collection.beatmaps {
+            writer.write_string(&beatmap.md5);
+        }
+    }
+    writer.into_inner()
+}
+
+/// Write a `collection.db` file, replacing it atomically
+pub fn write_collection_db(path: &Path, db: &CollectionDb) -> AppResult<()> {
+    let tmp_path = path.with_extension("db.tmp");
+    fs::write(&tmp_path, encode_collection_db(db))
+        .map_err(|e| AppError::io("write collection file", &tmp_path, e))?;
+    fs::rename(&tmp_path, path).map_err(|e| AppError::io("replace collection file", path, e))
+}
@@ -0,0 +1,7 @@
+pub mod collection;
+pub mod osdb;
+pub mod osu_db;
+mod reader;
+pub mod replay;
+pub mod user_config;
+mod writer;
@@ -0,0 +1,121 @@
+use super::collection::{Collection, CollectionBeatmap};
+use super::reader::OsuReader;
+use crate::core::error::{AppError, AppResult};
+use flate2::read::GzDecoder;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// Read and parse an `.osdb` collection export (Collection Manager format)
+pub fn read_osdb(path: &Path) -> AppResult<Vec<Collection>> {
+    let data = fs::read(path).map_err(|e| AppError::io("read osdb file", path, e))?;
+    parse_osdb(&data)
+        .map_err(|e| AppError::parse("osdb file", e).with_context(path.display().to_string()))
+}
+
+/// Parse the version header, e.g. `o!dm8` or `o!dm8min`
+fn parse_version(header: &str) -> Result<(u32, bool), String> {
+    let rest = header
+        .strip_prefix("o!dm")
+        .ok_or_else(|| format!("Unknown osdb header '{}'", header))?;
+    let (number, minimal) = match rest.strip_suffix("min") {
+        Some(number) => (number, true),
+        None => (rest, false),
+    };
+    let version = if number.is_empty() {
+        1
+    } else {
+        number
+            .parse::<u32>()
+            .map_err(|_| format!("Unknown osdb version '{}'", header))?
+    };
+    Ok((version, minimal))
+}
+
+pub fn parse_osdb(data: &[u8]) -> Result<Vec<Collection>, String> {
+    let mut reader = OsuReader::new(data);
+    let (version, minimal) = parse_version(&reader.read_dotnet_string()?)?;
+
+    // Since version 7 everything after the header is gzip-compressed
+    if version >= 7 {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(&data[data.len() - reader.remaining()..])
+            .read_to_end(&mut decompressed)
+            .map_err(|e| format!("Failed to decompress osdb body: {}", e))?;
+        let mut reader = OsuReader::new(&decompressed);
+        // The version header is repeated inside the compressed stream
+        reader.read_dotnet_string()?;
+        return parse_osdb_body(&mut reader, version, minimal);
+    }
+
+    parse_osdb_body(&mut reader, version, minimal)
+}
+
+fn parse_osdb_body(
+    reader: &mut OsuReader,
+    version: u32,
+    minimal: bool,
+) -> Result<Vec<Collection>, String> {
+    // Export date as an OLE automation date
+    reader.read_f64()?;
+    if version >= 8 {
+        // Last editor
+        reader.read_dotnet_string()?;
+    }
+
+    let count = reader.read_i32()?;
+    // Name (at least its length byte) and beatmap count
+    let mut collections = Vec::with_capacity(reader.capacity_for(count, 5));
+    for _ in 0..count {
+        let name = reader.read_dotnet_string()?;
+        if version >= 7 {
+            // Online collection ID
+            reader.read_i32()?;
+        }
+
+        let beatmap_count = reader.read_i32()?;
+        // Beatmap ID and md5 (at least its length byte)
+        let mut beatmaps = Vec::with_capacity(reader.capacity_for(beatmap_count, 5));
+        for _ in 0..beatmap_count {
+            let beatmap_id = reader.read_i32()?;
+            let beatmapset_id = if version >= 2 { reader.read_i32()? } else { -1 };
+            if !minimal {
+                // Artist, title and difficulty name
+                reader.read_dotnet_string()?;
+                reader.read_dotnet_string()?;
+                reader.read_dotnet_string()?;
+            }
+            let md5 = reader.read_dotnet_string()?;
+            if version >= 4 {
+                // User comment
+                reader.read_dotnet_string()?;
+            }
+            if version >= 8 || (version >= 5 && !minimal) {
+                // Play mode
+                reader.read_u8()?;
+            }
+            if version >= 8 || (version >= 6 && !minimal) {
+                // Star rating
+                reader.read_f64()?;
+            }
+
+            beatmaps.push(CollectionBeatmap {
+                md5,
+                beatmap_id: Some(beatmap_id).filter(|id| *id > 0),
+                beatmapset_id: Some(beatmapset_id).filter(|id| *id > 0),
+            });
+        }
+
+        // Hashes of maps unknown to the exporter
+        if version >= 3 {
+            let hash_count = reader.read_i32()?;
+            for _ in 0..hash_count {
+                beatmaps.push(CollectionBeatmap::from_md5(reader.read_dotnet_string()?));
+            }
+        }
+
+        collections.push(Collection { name, beatmaps });
+    }
+
+    Ok(collections)
+}
@@ -0,0 +1,225 @@
+use super::reader::OsuReader;
+use crate::core::error::{AppError, AppResult};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Lower bound of a beatmap entry's size, its fixed-size fields alone take more
+const MIN_BEATMAP_ENTRY_SIZE: usize = 64;
+/// First version without the per-beatmap entry size
+const VERSION_NO_ENTRY_SIZE: i32 = 20191106;
+/// First version storing difficulty settings as floats and star rating caches
+const VERSION_FLOAT_DIFFICULTY: i32 = 20140609;
+/// First version storing star ratings as floats instead of doubles
+const VERSION_FLOAT_STARS: i32 = 20250107;
+
+/// Cached star rating of a beatmap for a mod combination
+#[derive(Debug, Clone, Serialize)]
+pub struct CachedStars {
+    pub mods: i32,
+    pub stars: f64,
+}
+
+/// Star rating caches for each game mode
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StarCache {
+    pub osu: Vec<CachedStars>,
+    pub taiko: Vec<CachedStars>,
+    pub catch: Vec<CachedStars>,
+    pub mania: Vec<CachedStars>,
+}
+
+/// A beatmap difficulty entry of `osu!.db`
+#[derive(Debug, Clone, Serialize)]
+pub struct OsuDbBeatmap {
+    pub artist: String,
+    pub artist_unicode: String,
+    pub title: String,
+    pub title_unicode: String,
+    pub creator: String,
+    pub difficulty: String,
+    pub audio_file: String,
+    pub md5: String,
+    pub filename: String,
+    pub folder: String,
+    pub ranked_status: u8,
+    pub count_circles: u16,
+    pub count_sliders: u16,
+    pub count_spinners: u16,
+    pub ar: f32,
+    pub cs: f32,
+    pub hp: f32,
+    pub od: f32,
+    pub star_cache: StarCache,
+    /// Drain time in seconds
+    pub drain_time: i32,
+    /// Total time in milliseconds
+    pub total_time: i32,
+    pub beatmap_id: i32,
+    pub beatmapset_id: i32,
+    /// 0 = osu!, 1 = taiko, 2 = catch, 3 = mania
+    pub mode: u8,
+    pub source: String,
+    pub tags: String,
+}
+
+/// Parsed content of an osu! stable `osu!.db`
+#[derive(Debug, Clone)]
+pub struct OsuDb {
+    pub version: i32,
+    pub player_name: String,
+    pub beatmaps: Vec<OsuDbBeatmap>,
+}
+
+/// Read and parse an `osu!.db` file
+pub fn read_osu_db(path: &Path) -> AppResult<OsuDb> {
+    let data = fs::read(path).map_err(|e| AppError::io("read osu!.db", path, e))?;
+    parse_osu_db(&data)
+        .map_err(|e| AppError::parse("osu!.db", e).with_context(path.display().to_string()))
+}
+
+pub fn parse_osu_db(data: &[u8]) -> Result<OsuDb, String> {
+    let mut reader = OsuReader::new(data);
+    let version = reader.read_i32()?;
+    // Folder count, account unlocked, unlock date
+    reader.read_i32()?;
+    reader.read_bool()?;
+    reader.read_i64()?;
+    let player_name = reader.read_string()?;
+
+    let count = reader.read_i32()?;
+    let mut beatmaps = Vec::with_capacity(reader.capacity_for(count, MIN_BEATMAP_ENTRY_SIZE));
+    for _ in 0..count {
+        beatmaps.push(read_beatmap(&mut reader, version)?);
+    }
+
+    Ok(OsuDb {
+        version,
+        player_name,
+        beatmaps,
+    })
+}
+
+fn read_difficulty_value(reader: &mut OsuReader, version: i32) -> Result<f32, String> {
+    if version >= VERSION_FLOAT_DIFFICULTY {
+        reader.read_f32()
+    } else {
+        Ok(reader.read_u8()? as f32)
+    }
+}
+
+fn read_star_ratings(reader: &mut OsuReader, version: i32) -> Result<Vec<CachedStars>, String> {
+    let count = reader.read_i32()?;
+    // Two marker bytes, the mods and at least a 4 byte rating
+    let mut ratings = Vec::with_capacity(reader.capacity_for(count, 10));
+    for _ in 0..count {
+        // Each value is prefixed by a type marker byte
+        reader.read_u8()?;
+        let mods = reader.read_i32()?;
+        reader.read_u8()?;
+        let stars = if version >= VERSION_FLOAT_STARS {
+            reader.read_f32()? as f64
+        } else {
+            reader.read_f64()?
+        };
+        ratings.push(CachedStars { mods, stars });
+    }
+    Ok(ratings)
+}
+
+fn read_beatmap(reader: &mut OsuReader, version: i32) -> Result<OsuDbBeatmap, String> {
+    if version < VERSION_NO_ENTRY_SIZE {
+        reader.read_i32()?;
+    }
+
+    let artist = reader.read_string()?;
+    let artist_unicode = reader.read_string()?;
+    let title = reader.read_string()?;
+    let title_unicode = reader.read_string()?;
+    let creator = reader.read_string()?;
+    let difficulty = reader.read_string()?;
+    let audio_file = reader.read_string()?;
+    let md5 = reader.read_string()?;
+    let filename = reader.read_string()?;
+    let ranked_status = reader.read_u8()?;
+    let count_circles = reader.read_u16()?;
+    let count_sliders = reader.read_u16()?;
+    let count_spinners = reader.read_u16()?;
+    // Last modification time
+    reader.read_i64()?;
+    let ar = read_difficulty_value(reader, version)?;
+    let cs = read_difficulty_value(reader, version)?;
+    let hp = read_difficulty_value(reader, version)?;
+    let od = read_difficulty_value(reader, version)?;
+    // Slider velocity
+    reader.read_f64()?;
+
+    let star_cache = if version >= VERSION_FLOAT_DIFFICULTY {
+        StarCache {
+            osu: read_star_ratings(reader, version)?,
+            taiko: read_star_ratings(reader, version)?,
+            catch: read_star_ratings(reader, version)?,
+            mania: read_star_ratings(reader, version)?,
+        }
+    } else {
+        StarCache::default()
+    };
+
+    let drain_time = reader.read_i32()?;
+    let total_time = reader.read_i32()?;
+    // Audio preview time
+    reader.read_i32()?;
+
+    // Timing points: beat length, offset, uninherited flag
+    let timing_point_count = reader.read_i32()?;
+    reader.skip(timing_point_count.max(0) as usize * 17)?;
+
+    let beatmap_id = reader.read_i32()?;
+    let beatmapset_id = reader.read_i32()?;
+    // Thread ID, grades for the four modes, local offset, stack leniency
+    reader.skip(4 + 4 + 2 + 4)?;
+    let mode = reader.read_u8()?;
+    let source = reader.read_string()?;
+    let tags = reader.read_string()?;
+    // Online offset, title font, unplayed, last played, osz2
+    reader.skip(2)?;
+    reader.read_string()?;
+    reader.skip(1 + 8 + 1)?;
+    let folder = reader.read_string()?;
+    // Last repository check and the five override flags
+    reader.skip(8 + 5)?;
+    if version < VERSION_FLOAT_DIFFICULTY {
+        reader.skip(2)?;
+    }
+    // Last modification time and mania scroll speed
+    reader.skip(4 + 1)?;
+
+    Ok(OsuDbBeatmap {
+        artist,
+        artist_unicode,
+        title,
+        title_unicode,
+        creator,
+        difficulty,
+        audio_file,
+        md5,
+        filename,
+        folder,
+        ranked_status,
+        count_circles,
+        count_sliders,
+        count_spinners,
+        ar,
+        cs,
+        hp,
+        od,
+        star_cache,
+        drain_time,
+        total_time,
+        beatmap_id,
+        beatmapset_id,
+        mode,
+        source,
+        tags,
+    })
+}
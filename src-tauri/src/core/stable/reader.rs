@@ -0,0 +1,112 @@
+/// Cursor over the little-endian binary encoding used by osu! stable files
+pub struct OsuReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> OsuReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    /// Number of bytes left to read
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    /// Capacity to reserve for `count` entries of at least `min_entry_size` bytes
+    ///
+    /// Counts come from the file, a corrupt one must not make us allocate more than it can hold.
+    pub fn capacity_for(&self, count: i32, min_entry_size: usize) -> usize {
+        (count.max(0) as usize).min(self.remaining() / min_entry_size.max(1))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| format!("Unexpected end of data at offset {}", self.pos))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    pub fn skip(&mut self, len: usize) -> Result<(), String> {
+        self.take(len).map(|_| ())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, String> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, String> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_i32(&mut self) -> Result<i32, String> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, String> {
+        let bytes = self.take(8)?;
+        Ok(i64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    pub fn read_f32(&mut self) -> Result<f32, String> {
+        let bytes = self.take(4)?;
+        Ok(f32::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, String> {
+        let bytes = self.take(8)?;
+        Ok(f64::from_le_bytes(bytes.try_into().unwrap_or_default()))
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], String> {
+        self.take(len)
+    }
+
+    /// Read an unsigned LEB128 integer (also .NET's 7-bit encoded int)
+    pub fn read_uleb128(&mut self) -> Result<u64, String> {
+        let mut result: u64 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            if shift >= 64 {
+                return Err("ULEB128 value overflows 64 bits".to_string());
+            }
+            result |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+    }
+
+    /// Read an osu! string: `0x00` for absent, or `0x0b` + ULEB128 length + UTF-8
+    pub fn read_string(&mut self) -> Result<String, String> {
+        match self.read_u8()? {
+            0x00 => Ok(String::new()),
+            0x0b => self.read_dotnet_string(),
+            marker => Err(format!(
+                "Invalid string marker 0x{:02x} at offset {}",
+                marker,
+                self.pos - 1
+            )),
+        }
+    }
+
+    /// Read a .NET `BinaryReader` string: 7-bit encoded length + UTF-8
+    pub fn read_dotnet_string(&mut self) -> Result<String, String> {
+        let len = self.read_uleb128()? as usize;
+        let bytes = self.take(len)?;
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+}
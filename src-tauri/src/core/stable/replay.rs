@@ -0,0 +1,152 @@
+use super::reader::OsuReader;
+use crate::core::error::{AppError, AppResult};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// Marker of the trailing frame holding the RNG seed
+const SEED_FRAME_DELTA: i64 = -12345;
+
+/// Judgement counts stored in a replay
+///
+/// In osu!mania `gekis` are MAX (320) and `katus` are 200 judgements.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayJudgements {
+    pub count_300: u16,
+    pub count_100: u16,
+    pub count_50: u16,
+    pub count_geki: u16,
+    pub count_katu: u16,
+    pub count_miss: u16,
+}
+
+/// A single input frame with its absolute time in milliseconds
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayFrame {
+    pub time: f64,
+    /// osu!mania: bitmask of pressed columns
+    pub x: f32,
+}
+
+/// Parsed content of an `.osr` replay file
+#[derive(Debug, Clone, Serialize)]
+pub struct Replay {
+    pub mode: u8,
+    pub game_version: i32,
+    pub beatmap_md5: String,
+    pub player_name: String,
+    pub judgements: ReplayJudgements,
+    pub score: i32,
+    pub max_combo: u16,
+    pub perfect: bool,
+    pub mods: u32,
+    /// Windows ticks (100ns since 0001-01-01)
+    pub timestamp: i64,
+    #[serde(skip)]
+    pub frames: Vec<ReplayFrame>,
+    pub online_score_id: i64,
+}
+
+/// Read and parse an `.osr` file, including its LZMA compressed frames
+pub fn read_replay(path: &Path) -> AppResult<Replay> {
+    read_replay_file(path, true)
+}
+
+/// Read only the replay header, skipping frame decompression
+pub fn read_replay_header(path: &Path) -> AppResult<Replay> {
+    read_replay_file(path, false)
+}
+
+fn read_replay_file(path: &Path, with_frames: bool) -> AppResult<Replay> {
+    let data = fs::read(path).map_err(|e| AppError::io("read replay", path, e))?;
+    parse_replay(&data, with_frames)
+        .map_err(|e| AppError::parse("replay", e).with_context(path.display().to_string()))
+}
+
+pub fn parse_replay(data: &[u8], with_frames: bool) -> Result<Replay, String> {
+    let mut reader = OsuReader::new(data);
+    let mode = reader.read_u8()?;
+    let game_version = reader.read_i32()?;
+    let beatmap_md5 = reader.read_string()?;
+    let player_name = reader.read_string()?;
+    // Replay md5
+    reader.read_string()?;
+    let judgements = ReplayJudgements {
+        count_300: reader.read_u16()?,
+        count_100: reader.read_u16()?,
+        count_50: reader.read_u16()?,
+        count_geki: reader.read_u16()?,
+        count_katu: reader.read_u16()?,
+        count_miss: reader.read_u16()?,
+    };
+    let score = reader.read_i32()?;
+    let max_combo = reader.read_u16()?;
+    let perfect = reader.read_bool()?;
+    let mods = reader.read_i32()? as u32;
+    // Life bar graph
+    reader.read_string()?;
+    let timestamp = reader.read_i64()?;
+
+    let compressed_len = reader.read_i32()?.max(0) as usize;
+    let compressed = reader.read_bytes(compressed_len)?;
+    let frames = if with_frames {
+        decode_frames(compressed)?
+    } else {
+        Vec::new()
+    };
+    let online_score_id = reader.read_i64().unwrap_or(0);
+
+    Ok(Replay {
+        mode,
+        game_version,
+        beatmap_md5,
+        player_name,
+        judgements,
+        score,
+        max_combo,
+        perfect,
+        mods,
+        timestamp,
+        frames,
+        online_score_id,
+    })
+}
+
+/// Decompress and parse `w|x|y|z` frames separated by commas
+///
+/// Only `x` is kept: it holds the pressed column bitmask in osu!mania.
+fn decode_frames(compressed: &[u8]) -> Result<Vec<ReplayFrame>, String> {
+    if compressed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut raw = Vec::new();
+    lzma_rs::lzma_decompress(&mut std::io::Cursor::new(compressed), &mut raw)
+        .map_err(|e| format!("Failed to decompress replay frames: {}", e))?;
+    let text = String::from_utf8_lossy(&raw);
+
+    let mut frames = Vec::new();
+    let mut time: i64 = 0;
+    for frame in text.split(',').filter(|frame| !frame.is_empty()) {
+        let mut parts = frame.split('|');
+        let delta = parts
+            .next()
+            .and_then(|w| w.trim().parse::<i64>().ok())
+            .ok_or_else(|| format!("Invalid replay frame '{}'", frame))?;
+        if delta == SEED_FRAME_DELTA {
+            continue;
+        }
+        let x = parts
+            .next()
+            .and_then(|v| v.parse::<f32>().ok())
+            .unwrap_or(0.0);
+
+        time += delta;
+        frames.push(ReplayFrame {
+            time: time as f64,
+            x,
+        });
+    }
+
+    Ok(frames)
+}
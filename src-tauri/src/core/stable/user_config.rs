@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Settings of an osu! stable install, read from `osu!.<user>.cfg`
+#[derive(Debug, Clone, Default)]
+pub struct UserConfig {
+    values: HashMap<String, String>,
+}
+
+impl UserConfig {
+    /// Load the most recently written `osu!.<user>.cfg` of the install at `osu_dir`
+    pub fn load(osu_dir: &Path) -> Option<Self> {
+        let path = fs::read_dir(osu_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| is_user_config(&entry.file_name().to_string_lossy()))
+            .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())?
+            .path();
+        let contents = fs::read_to_string(&path).ok()?;
+        Some(Self {
+            values: parse(&contents),
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Build the client was last run with, e.g. `b20250107.1`
+    pub fn client_version(&self) -> Option<&str> {
+        self.get("LastVersion")
+    }
+
+    /// Songs folder set in the options, relative to the install unless absolute
+    pub fn beatmap_directory(&self) -> Option<&str> {
+        self.get("BeatmapDirectory").filter(|dir| !dir.is_empty())
+    }
+}
+
+/// `osu!.cfg` holds machine settings, only `osu!.<user>.cfg` has the user's
+fn is_user_config(file_name: &str) -> bool {
+    let lower = file_name.to_lowercase();
+    lower.starts_with("osu!.") && lower.ends_with(".cfg") && lower != "osu!.cfg"
+}
+
+/// Lines are `Key = Value`, comments start with `#`
+fn parse(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
@@ -0,0 +1,45 @@
+/// Buffer writing the little-endian binary encoding used by osu! stable files
+pub struct OsuWriter {
+    data: Vec<u8>,
+}
+
+impl OsuWriter {
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_uleb128(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.write_u8(byte);
+                return;
+            }
+            self.write_u8(byte | 0x80);
+        }
+    }
+
+    /// Write an osu! string: `0x00` when empty, otherwise `0x0b` + ULEB128 length + UTF-8
+    pub fn write_string(&mut self, value: &str) {
+        if value.is_empty() {
+            self.write_u8(0x00);
+            return;
+        }
+        self.write_u8(0x0b);
+        self.write_uleb128(value.len() as u64);
+        self.data.extend_from_slice(value.as_bytes());
+    }
+}
@@ -4,20 +4,11 @@ mod core;
 use core::beatmap::monitoring::CurrentBeatmapWithRates;
 use core::internal::CurrentBeatmapData;
 use core::packmaker::{SharedPackMaker, PackMaker};
-use core::connection::monitoring::start_monitoring;
+use core::connection::{ConnectionMonitor, SharedMonitor};
 use std::sync::Arc;
-use tauri::{AppHandle, Manager};
+use tauri::Manager;
 use tokio::sync::Mutex;
 
-/// Spawn initial monitoring thread on app startup
-fn spawn_initial_monitoring(app_handle: AppHandle, current_beatmap: CurrentBeatmapWithRates) {
-    std::thread::spawn(move || {
-        tauri::async_runtime::block_on(async {
-            start_monitoring(app_handle, current_beatmap).await;
-        });
-    });
-}
-
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
@@ -28,6 +19,9 @@ pub fn run() {
                 beatmap_info: None,
                 rates: Vec::new(),
                 nps_data: None,
+                density_data: None,
+                pattern_data: None,
+                bpm: None,
             }));
             app.manage(current_beatmap.clone());
 
@@ -35,10 +29,13 @@ pub fn run() {
             let pack: SharedPackMaker = Arc::new(Mutex::new(PackMaker::new()));
             app.manage(pack.clone());
 
-            println!("✅ App initialized");
+            // Start monitoring in background, the monitor is kept in state to restart it later
+            let mut monitor = ConnectionMonitor::default();
+            monitor.start(app.handle().clone(), current_beatmap);
+            let monitor: SharedMonitor = Arc::new(Mutex::new(monitor));
+            app.manage(monitor);
 
-            // Start monitoring in background
-            spawn_initial_monitoring(app.handle().clone(), current_beatmap);
+            println!("✅ App initialized");
 
             Ok(())
         })
@@ -46,16 +43,37 @@ pub fn run() {
             crate::commands::beatmap::get_current_beatmap,
             crate::commands::beatmap::get_all_rates,
             crate::commands::beatmap::get_current_nps,
+            crate::commands::beatmap::get_current_density,
+            crate::commands::beatmap::get_current_patterns,
+            crate::commands::beatmap::get_current_difficulty_timeline,
             crate::commands::beatmap::apply_beatmap_modifications,
             crate::commands::beatmap::emit_demo_beatmap,
             crate::commands::beatmap::calculate_nps_from_beatmap_url,
+            crate::commands::collection::list_collections,
+            crate::commands::collection::create_pack_from_collection,
+            crate::commands::collection::download_missing_from_collection,
             crate::commands::connection::restart_osu_connection,
+            crate::commands::connection::stop_osu_connection,
             crate::commands::download::download_beatmap_from_url,
+            crate::commands::download::download_beatmapsets,
+            crate::commands::download::download_pack_manifest,
             crate::commands::download::test_download_event,
+            crate::commands::library::get_library,
+            crate::commands::library::resolve_beatmap_md5,
+            crate::commands::profile::get_player_profile,
+            crate::commands::recommend::recommend_maps,
             crate::commands::preferences::get_songs_path,
             crate::commands::preferences::set_songs_path,
             crate::commands::preferences::get_theme,
             crate::commands::preferences::set_theme,
+            crate::commands::preferences::get_write_generated_to_collection,
+            crate::commands::preferences::set_write_generated_to_collection,
+            crate::commands::preferences::get_generated_collection_name,
+            crate::commands::preferences::set_generated_collection_name,
+            crate::commands::replay::list_replays,
+            crate::commands::replay::analyze_replay,
+            crate::commands::replay::rescore_replay,
+            crate::commands::replay::rescore_offsets,
             crate::commands::packmaker::add_to_pack,
             crate::commands::packmaker::get_pack,
             crate::commands::packmaker::update_pack_metadata,
@@ -54,8 +54,26 @@ const CurrentBeatmapDetail: React.FC = () => {
 
   // No beatmap currently playing
   if (!beatmapset) {
-    // If we have an explicit connection error (not just initial disconnected state), show reconnect UI
-    if (connectionStatus.error && connectionStatus.error !== "Please start osu! to see beatmaps here") {
+    // Monitoring was stopped on request, offer to start it again
+    if (connectionStatus.state === 'stopped') {
+      return (
+        <div className="flex flex-col items-center justify-center py-16 gap-4">
+          <div className="text-6xl">⏸️</div>
+          <h2 className="text-2xl font-bold text-base-content">osu! monitoring stopped</h2>
+          <button
+            type="button"
+            className="btn btn-primary"
+            onClick={reconnect}
+          >
+            Start monitoring
+          </button>
+        </div>
+      )
+    }
+
+    // If a previous connection was lost, show reconnect UI while osu! is searched for again
+    if (connectionStatus.error) {
+      const searching = connectionStatus.state === 'searching'
       return (
         <div className="flex flex-col items-center justify-center py-16 gap-4">
           <div className="text-6xl">⚠️</div>
@@ -67,16 +85,9 @@ const CurrentBeatmapDetail: React.FC = () => {
             type="button"
             className="btn btn-primary"
             onClick={reconnect}
-            disabled={connectionStatus.error === 'Reconnecting...'}
           >
-            {connectionStatus.error === 'Reconnecting...' ? (
-              <>
-                <span className="loading loading-spinner loading-sm"></span>
-                Reconnecting...
-              </>
-            ) : (
-              'Retry Connection'
-            )}
+            {searching && <span className="loading loading-spinner loading-sm"></span>}
+            {searching ? 'Retry now' : 'Retry Connection'}
           </button>
           <p className="text-sm text-base-content/50 mt-2">
             Make sure osu! is running and try again
@@ -91,6 +102,14 @@ const CurrentBeatmapDetail: React.FC = () => {
         <div className="text-6xl">🎵</div>
         <h2 className="text-2xl font-bold text-base-content">No beatmap currently playing</h2>
         <p className="text-base-content/70">Open a beatmap in osu! to see it here</p>
+        {connectionStatus.details && (
+          <p className="text-xs text-base-content/50">
+            osu! {connectionStatus.details.client_version ?? 'unknown version'} · pid {connectionStatus.details.pid}
+            {connectionStatus.details.game_state && ` · ${connectionStatus.details.game_state}`}
+            {connectionStatus.details.read_latency_ms !== null &&
+              ` · ${connectionStatus.details.read_latency_ms.toFixed(1)} ms`}
+          </p>
+        )}
       </div>
     )
   }
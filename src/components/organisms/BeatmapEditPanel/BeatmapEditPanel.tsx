@@ -1,8 +1,9 @@
 import type React from 'react'
 import { useEffect, useMemo, useState, useId } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { errorMessage } from '@/utils/error'
 import { Button, SliderInput, CentirateInput, Select, Input } from '@/components/atoms'
-import type { Beatmapset } from '@/types/beatmap/detail'
+import type { CollectionWrite, GeneratedBeatmap } from '@/types/beatmap/detail'
 
 interface BeatmapEditPanelProps {
   isOpen: boolean
@@ -26,6 +27,17 @@ export interface BeatmapModifications {
   ln_min_distance_ms?: number
 }
 
+function describeCollectionWrite(write: CollectionWrite | null): string | null {
+  switch (write?.status) {
+    case 'queued':
+      return `Beatmap created. It will be added to "${write.collection}" once osu! is closed.`
+    case 'failed':
+      return `Beatmap created, but it could not be added to "${write.collection}": ${write.message}`
+    default:
+      return null
+  }
+}
+
 const BeatmapEditPanel: React.FC<BeatmapEditPanelProps> = ({
   isOpen,
   onClose,
@@ -52,6 +64,7 @@ const BeatmapEditPanel: React.FC<BeatmapEditPanelProps> = ({
   const [lnMinDistanceMs, setLnMinDistanceMs] = useState(40)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
+  const [notice, setNotice] = useState<string | null>(null)
 
   // Generate unique id for target BPM input for a11y
   const targetBpmInputId = useId()
@@ -85,13 +98,20 @@ const BeatmapEditPanel: React.FC<BeatmapEditPanelProps> = ({
     try {
       setLoading(true)
       setError(null)
-      const modifiedBeatmap = await invoke<Beatmapset>('apply_beatmap_modifications', {
+      setNotice(null)
+      const generated = await invoke<GeneratedBeatmap>('apply_beatmap_modifications', {
         modifications,
       })
-      console.log('Modified beatmap:', modifiedBeatmap)
-      onClose()
+      console.log('Modified beatmap:', generated.beatmapset)
+      // Keep the panel open to report a collection entry that was not written yet
+      const collectionNotice = describeCollectionWrite(generated.collection)
+      if (collectionNotice) {
+        setNotice(collectionNotice)
+      } else {
+        onClose()
+      }
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
     } finally {
       setLoading(false)
     }
@@ -260,6 +280,12 @@ const BeatmapEditPanel: React.FC<BeatmapEditPanelProps> = ({
               </div>
             )}
 
+            {notice && (
+              <div className="alert alert-info">
+                <span>{notice}</span>
+              </div>
+            )}
+
             {/* Action Buttons */}
             <div className="flex gap-3 pt-4">
               <Button
@@ -6,7 +6,9 @@ import { formatBytes } from '@/utils/format'
 
 // Types must match the Rust backend types
 export interface DownloadStatus {
-  beatmapset_id: number
+  job_id: number
+  group_id?: number
+  beatmapset_id?: number
   filename: string
   display_name: string
   status: string // Will be "Queued" | "Downloading" | "Completed" | "Failed"
@@ -102,7 +104,7 @@ const DownloadPanel: React.FC = () => {
         ) : (
           <div className="p-2">
             {downloads.map((download) => (
-              <div key={download.beatmapset_id} className="mb-3 bg-base-200 rounded-lg p-3">
+              <div key={download.job_id} className="mb-3 bg-base-200 rounded-lg p-3">
                 <div className="flex items-center justify-between mb-2">
                   <div className="flex items-center gap-2 flex-1 min-w-0">
                     {getStatusIcon(download.status)}
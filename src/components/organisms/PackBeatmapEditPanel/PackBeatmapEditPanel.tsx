@@ -1,5 +1,6 @@
 import React, { useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { errorMessage } from '@/utils/error'
 import { Button, Input } from '@/components/atoms'
 import { SideMenu } from '@/components/molecules'
 
@@ -49,7 +50,7 @@ const PackBeatmapEditPanel: React.FC<PackBeatmapEditPanelProps> = ({
       onBeatmapUpdated()
       onClose()
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
     } finally {
       setLoading(false)
     }
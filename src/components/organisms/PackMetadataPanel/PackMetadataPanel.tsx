@@ -1,5 +1,6 @@
 import React, { useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { errorMessage } from '@/utils/error'
 import { Button, Input } from '@/components/atoms'
 import { SideMenu } from '@/components/molecules'
 
@@ -34,7 +35,7 @@ const PackMetadataPanel: React.FC<PackMetadataPanelProps> = ({
       onMetadataChange(metadata)
       onClose()
     } catch (err) {
-      setError(err instanceof Error ? err.message : String(err))
+      setError(errorMessage(err))
     } finally {
       setLoading(false)
     }
@@ -1,7 +1,7 @@
 import React, { useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { CurrentBeatmapDetail } from '@/components/organisms'
-import { useTauriEvents } from '@/context/TauriEventProvider'
+import { useTauriEvents, type ConnectionDetails } from '@/context/TauriEventProvider'
 
 // Context for current beatmap page state
 interface CurrentBeatmapContextType {
@@ -9,7 +9,12 @@ interface CurrentBeatmapContextType {
   setCentirate: (value: number) => void
   ratingType: string
   setRatingType: (value: string) => void
-  connectionStatus: { connected: boolean; error?: string }
+  connectionStatus: {
+    state: 'searching' | 'connected' | 'lost' | 'stopped'
+    connected: boolean
+    error?: string
+    details?: ConnectionDetails | null
+  }
   reconnect: () => Promise<void>
 }
 
@@ -1,5 +1,6 @@
 import React, { useCallback, useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { errorMessage } from '@/utils/error'
 import { useTauriEvents } from '@/context/TauriEventProvider'
 import { Header, PackMetadataPanel, PackBeatmapEditPanel } from '@/components/organisms'
 import { Button } from '@/components/atoms'
@@ -44,7 +45,7 @@ const PackMakerPage: React.FC = () => {
         setPackBeatmaps(result[1] || [])
       }
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e))
+      setError(errorMessage(e))
     }
   }, [])
 
@@ -59,7 +60,7 @@ const PackMakerPage: React.FC = () => {
       await invoke('add_to_pack')
       await loadPack()
     } catch (e) {
-      setError(e instanceof Error ? e.message : String(e))
+      setError(errorMessage(e))
     } finally {
       setLoading(false)
     }
@@ -1,9 +1,15 @@
 import React, { useState, useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { MdFolder, MdSave } from 'react-icons/md'
+import { useTauriEvents } from '@/context/TauriEventProvider'
+import { errorMessage } from '@/utils/error'
 
 const SettingsPage: React.FC = () => {
+  const { connectionStatus } = useTauriEvents()
   const [songsPath, setSongsPath] = useState('')
+  const [savedSongsPath, setSavedSongsPath] = useState('')
+  const [writeToCollection, setWriteToCollection] = useState(false)
+  const [collectionName, setCollectionName] = useState('')
   const [loading, setLoading] = useState(true)
   const [saving, setSaving] = useState(false)
   const [message, setMessage] = useState<{ type: 'success' | 'error', text: string } | null>(null)
@@ -16,6 +22,9 @@ const SettingsPage: React.FC = () => {
     try {
       const path = await invoke<string>('get_songs_path')
       setSongsPath(path)
+      setSavedSongsPath(path)
+      setWriteToCollection(await invoke<boolean>('get_write_generated_to_collection'))
+      setCollectionName(await invoke<string>('get_generated_collection_name'))
     } catch (error) {
       console.error('Failed to load settings:', error)
     } finally {
@@ -28,16 +37,39 @@ const SettingsPage: React.FC = () => {
     setMessage(null)
 
     try {
-      await invoke('set_songs_path', { path: songsPath })
+      // Saved first so a rejected Songs folder does not discard the collection options
+      await invoke('set_write_generated_to_collection', { enabled: writeToCollection })
+      await invoke('set_generated_collection_name', { name: collectionName })
+      if (songsPath !== savedSongsPath) {
+        await invoke('set_songs_path', { path: songsPath })
+        setSavedSongsPath(songsPath)
+      }
       setMessage({ type: 'success', text: 'Settings saved successfully!' })
     } catch (error) {
-      setMessage({ type: 'error', text: `Failed to save: ${error}` })
+      setMessage({ type: 'error', text: `Failed to save: ${errorMessage(error)}` })
       console.error('Failed to save settings:', error)
     } finally {
       setSaving(false)
     }
   }
 
+  // Songs folder of the running osu! client, offered when it differs from the saved one
+  const detectedSongsPath = connectionStatus.details?.songs_folder ?? null
+
+  const applyDetectedSongsPath = async () => {
+    if (!detectedSongsPath) return
+    setMessage(null)
+
+    try {
+      await invoke('set_songs_path', { path: detectedSongsPath })
+      setSongsPath(detectedSongsPath)
+      setSavedSongsPath(detectedSongsPath)
+      setMessage({ type: 'success', text: 'Songs folder saved from osu!' })
+    } catch (error) {
+      setMessage({ type: 'error', text: `Failed to save: ${errorMessage(error)}` })
+    }
+  }
+
   if (loading) {
     return (
       <div className="flex justify-center items-center py-16">
@@ -73,6 +105,41 @@ const SettingsPage: React.FC = () => {
                 Path where beatmaps will be downloaded
               </span>
             </label>
+            {detectedSongsPath && detectedSongsPath !== savedSongsPath && (
+              <div className="alert alert-info mt-2">
+                <span>
+                  osu! is using <code>{detectedSongsPath}</code>
+                </span>
+                <button type="button" className="btn btn-sm" onClick={applyDetectedSongsPath}>
+                  Use this folder
+                </button>
+              </div>
+            )}
+          </div>
+
+          <div className="form-control w-full mt-2">
+            <label className="label cursor-pointer justify-start gap-3">
+              <input
+                type="checkbox"
+                className="toggle toggle-primary"
+                checked={writeToCollection}
+                onChange={(e) => setWriteToCollection(e.target.checked)}
+              />
+              <span className="label-text">Add generated maps to an osu! collection</span>
+            </label>
+            <input
+              type="text"
+              placeholder="Collection name"
+              className="input input-bordered w-full"
+              value={collectionName}
+              disabled={!writeToCollection}
+              onChange={(e) => setCollectionName(e.target.value)}
+            />
+            <label className="label">
+              <span className="label-text-alt">
+                collection.db is backed up before writing and is never modified while osu! is running
+              </span>
+            </label>
           </div>
 
           {message && (
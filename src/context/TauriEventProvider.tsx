@@ -1,11 +1,24 @@
 import React from 'react'
 import { useEffect, useMemo, useState } from 'react'
 import { listen } from '@tauri-apps/api/event'
-import type { Beatmapset, Rates, NpsData } from '@/types/beatmap/detail'
+import type { Beatmapset, BpmStats, Rates, NpsData } from '@/types/beatmap/detail'
+
+type ConnectionState = 'searching' | 'connected' | 'lost' | 'stopped'
+
+export interface ConnectionDetails {
+  pid: number
+  client_version: string | null
+  game_state: string | null
+  songs_folder: string | null
+  last_heartbeat: number | null // unix time in ms
+  read_latency_ms: number | null
+}
 
 interface ConnectionStatus {
+  state: ConnectionState
   connected: boolean
-  error?: string
+  error?: string // why the last connection was lost, kept while searching again
+  details?: ConnectionDetails | null // only while connected
 }
 
 interface TauriEventState {
@@ -35,6 +48,7 @@ export const TauriEventProvider: React.FC<{ children: React.ReactNode }> = ({ ch
   const [currentRates, setCurrentRates] = useState<Rates[]>([])
   const [currentNpsData, setCurrentNpsData] = useState<NpsData | null>(null)
   const [connectionStatus, setConnectionStatus] = useState<ConnectionStatus>({
+    state: 'searching',
     connected: false,
     error: undefined
   })
@@ -59,6 +73,17 @@ export const TauriEventProvider: React.FC<{ children: React.ReactNode }> = ({ ch
       })
       unlistenRates.then(fn => unsubscribers.push(fn))
 
+      // BPM stats come with the calculation, after the beatmap itself
+      const unlistenBpm = listen<BpmStats>('bpm-calculated', (event) => {
+        setCurrentBeatmapset(prev => prev && {
+          ...prev,
+          beatmaps: prev.beatmaps.map((beatmap, index) =>
+            index === 0 ? { ...beatmap, bpm: event.payload } : beatmap
+          ),
+        })
+      })
+      unlistenBpm.then(fn => unsubscribers.push(fn))
+
       // Listen for NPS data
       const unlistenNps = listen<NpsData>('nps-calculated', (event) => {
         console.log('📈 Global received nps-calculated:', {
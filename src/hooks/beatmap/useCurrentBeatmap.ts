@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
+import { errorMessage } from '@/utils/error'
 import { listen } from '@tauri-apps/api/event'
 import type { Beatmapset } from '@/types/beatmap/detail'
 
@@ -15,7 +16,7 @@ export const useCurrentBeatmap = () => {
       const result = await invoke<Beatmapset | null>('get_current_beatmap')
       setBeatmapset(result)
     } catch (err) {
-      setError(err instanceof Error ? err : new Error(String(err)))
+      setError(err instanceof Error ? err : new Error(errorMessage(err)))
     } finally {
       setLoading(false)
     }
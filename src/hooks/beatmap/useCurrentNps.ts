@@ -1,8 +1,9 @@
 import { useEffect, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import type { NpsData } from '@/types/beatmap/detail'
+import { errorMessage } from '@/utils/error'
+import type { NpsData, NpsOptions } from '@/types/beatmap/detail'
 
-export const useCurrentNps = () => {
+export const useCurrentNps = (options?: NpsOptions) => {
   const [npsData, setNpsData] = useState<NpsData | null>(null)
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<Error | null>(null)
@@ -11,10 +12,10 @@ export const useCurrentNps = () => {
     try {
       setLoading(true)
       setError(null)
-      const result = await invoke<NpsData | null>('get_current_nps')
+      const result = await invoke<NpsData | null>('get_current_nps', { options: options ?? null })
       setNpsData(result)
     } catch (err) {
-      setError(err instanceof Error ? err : new Error(String(err)))
+      setError(err instanceof Error ? err : new Error(errorMessage(err)))
     } finally {
       setLoading(false)
     }
@@ -22,7 +23,7 @@ export const useCurrentNps = () => {
 
   useEffect(() => {
     fetchCurrentNps()
-  }, [])
+  }, [options?.buckets, options?.window_seconds, options?.smoothing, options?.rate])
 
   return {
     npsData,
@@ -12,14 +12,13 @@ export const useDownload = () => {
 
     try {
       const downloadUrl = `https://catboy.best/d/${beatmapset_osu_id}`
-      const filename = `${beatmapset_osu_id}.osz`
 
       console.log(`🎯 Download button clicked for beatmap ${beatmapset_osu_id}`)
       console.log(`📡 Calling Tauri command with URL: ${downloadUrl}`)
 
       const result = await invoke<string>('download_beatmap_from_url', {
         url: downloadUrl,
-        filename: filename,
+        beatmapsetId: beatmapset_osu_id,
         beatmapsetName: beatmapsetName || 'Unknown',
         creator: creator || 'Unknown'
       })
@@ -30,6 +30,19 @@ export interface BeatmapInfo {
   od: number
   hp: number
   ratings: RatingInfo[]
+  bpm?: BpmStats
+}
+
+export interface BpmPoint {
+  time: number
+  bpm: number
+}
+
+export interface BpmStats {
+  min: number
+  max: number
+  dominant: number
+  series: BpmPoint[]
 }
 
 export interface RatingInfo {
@@ -39,7 +52,6 @@ export interface RatingInfo {
 
 export interface Rates {
   id?: number
-  osu_hash?: string
   centirate: number
   drain_time: number // secondes
   total_time: number
@@ -53,9 +65,52 @@ export interface Rating {
   rating: number
   rating_type: string
   mode_rating: ModeRating
+  pp?: PpValue[] // osu ratings only
+}
+
+export interface PpValue {
+  accuracy: number // percent
+  pp: number
+}
+
+export interface LivePerformance {
+  centirate: number
+  stars: number
+  pp: number
+  passed_objects: number
 }
 
-export type ModeRating = { Mania: ManiaRating } | 'Std' | 'Ctb' | 'Taiko'
+export type ModeRating =
+  | { Mania: ManiaRating }
+  | { Std: StdRating }
+  | { Ctb: CtbRating }
+  | { Taiko: TaikoRating }
+
+export interface StdRating {
+  aim: number
+  speed: number
+  flashlight: number
+  slider_factor: number
+  speed_note_count: number
+  ar: number
+  max_combo: number
+}
+
+export interface TaikoRating {
+  stamina: number
+  rhythm: number
+  color: number
+  reading: number
+  max_combo: number
+}
+
+export interface CtbRating {
+  ar: number
+  fruits: number
+  droplets: number
+  tiny_droplets: number
+  max_combo: number
+}
 
 export interface ManiaRating {
   id?: number
@@ -81,3 +136,102 @@ export interface NpsData {
   nps_graph: number[]
   drain_time: number
 }
+
+export interface NpsOptions {
+  buckets?: number
+  window_seconds?: number
+  smoothing?: number
+  rate?: number
+}
+
+export interface DifficultySection {
+  start_time: number
+  end_time: number
+  overall: number
+  skillsets: ManiaRating
+}
+
+export interface DifficultyTimeline {
+  centirate: number
+  window_seconds: number
+  drain_time: number
+  sections: DifficultySection[]
+}
+
+export interface TimelineOptions {
+  sections?: number
+  window_seconds?: number
+  rate?: number
+}
+
+export type PatternKind =
+  | 'stream'
+  | 'jumpstream'
+  | 'handstream'
+  | 'chordjack'
+  | 'jacks'
+  | 'rolls'
+  | 'trills'
+  | 'brackets'
+  | 'ln_release'
+
+export interface PatternSegment {
+  pattern: PatternKind
+  start_time: number
+  end_time: number
+  notes: number
+}
+
+export interface PatternShare {
+  pattern: PatternKind
+  notes: number
+  percentage: number
+}
+
+export interface PatternData {
+  segments: PatternSegment[]
+  summary: PatternShare[]
+}
+
+export interface PeakDensity {
+  window: number
+  nps: number
+  start_time: number
+}
+
+export interface DensityData {
+  key_count: number
+  drain_time: number
+  column_nps: number[][]
+  left_hand_nps: number[]
+  right_hand_nps: number[]
+  chord_distribution: number[]
+  column_jacks: number[]
+  jack_count: number
+  ln_coverage: number[]
+  peak_density: PeakDensity[]
+}
+
+export type CalcError =
+  | { kind: 'missing_file'; path: string; message: string }
+  | { kind: 'parse'; path: string; message: string }
+  | { kind: 'calc'; message: string }
+  | { kind: 'cancelled' }
+
+// Payload of the 'calc-error' event
+export interface CalcFailure {
+  md5: string
+  error: CalcError
+  message: string
+}
+
+// What happened to a generated map's osu! collection entry
+export type CollectionWrite =
+  | { status: 'added'; collection: string }
+  | { status: 'queued'; collection: string } // written once osu! exits
+  | { status: 'failed'; collection: string; message: string }
+
+export interface GeneratedBeatmap {
+  beatmapset: Beatmapset
+  collection: CollectionWrite | null
+}
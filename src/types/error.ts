@@ -0,0 +1,18 @@
+// Error returned by every Tauri command
+export type ErrorCode =
+  | 'no_beatmap'
+  | 'file_not_found'
+  | 'io'
+  | 'parse'
+  | 'calc'
+  | 'osu_not_running'
+  | 'memory'
+  | 'network'
+  | 'invalid_input'
+  | 'internal'
+
+export interface AppError {
+  code: ErrorCode
+  message: string
+  context?: string // e.g. the path of the file that failed to load
+}
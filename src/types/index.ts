@@ -1,2 +1,3 @@
 export * as beatmap from './beatmap'
 export * from './common'
+export * from './error'
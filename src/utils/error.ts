@@ -0,0 +1,15 @@
+import type { AppError } from '@/types/error'
+
+export function isAppError(err: unknown): err is AppError {
+  return typeof err === 'object' && err !== null && 'code' in err && 'message' in err
+}
+
+/**
+ * Readable message for an error thrown by `invoke` or elsewhere
+ */
+export function errorMessage(err: unknown): string {
+  if (isAppError(err)) {
+    return err.context ? `${err.message} (${err.context})` : err.message
+  }
+  return err instanceof Error ? err.message : String(err)
+}
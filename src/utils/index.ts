@@ -1,3 +1,4 @@
 export * from './format'
+export * from './error'
 export * from './beatmap'
 